@@ -27,7 +27,6 @@ pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
         + "-command";
 
     // Initialize variables for command generation
-    let mut parameter_index: usize = 0;
     let mut command_choices_code = quote! {}; // Code for command choices (like enums)
     let mut parameter_conversion_code = quote! {}; // Code for converting input parameters
     let mut parameter_names = Vec::new(); // List of parameter names
@@ -240,9 +239,10 @@ pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
 
                                     param_conversion_code = generate_option_converter(
                                         param_name.to_token_stream().to_string(),
+                                        &command_locale_key,
+                                        &param_locale_key,
                                         param_type_str,
                                         &inner_type,
-                                        parameter_index,
                                         if is_required {
                                             None
                                         } else {
@@ -266,14 +266,14 @@ pub fn slash_command(attr: TokenStream, item: TokenStream) -> TokenStream {
 
                             param_conversion_code = generate_option_converter(
                                 param_name.to_token_stream().to_string(),
+                                &command_locale_key,
+                                &param_locale_key,
                                 param_type_str,
                                 &ty,
-                                parameter_index,
                                 None,
                             );
                         }
 
-                        parameter_index += 1;
                         parameter_conversion_code = quote! {
                             #parameter_conversion_code
                             #param_conversion_code
@@ -414,9 +414,10 @@ fn generate_option_token_stream(
 // Generates the code to convert a command input into the expected parameter type.
 fn generate_option_converter(
     option_name: String,  // The name of the option (parameter).
+    command_key: &String, // Command identifier used for localization keys.
+    option_key: &String,  // The option identifier used for localization keys.
     option_type: &str,    // The type of the option (e.g., String, Integer).
     resolved_type: &Type, // The actual resolved type in the Rust function.
-    option_index: usize,  // The index of the option in the command.
     default_value: Option<proc_macro2::TokenStream>, // An optional default value if the input is missing.
 ) -> proc_macro2::TokenStream {
     // Create identifiers based on the option type and option name
@@ -484,12 +485,18 @@ fn generate_option_converter(
         }
     };
 
-    // Generate the main part of the option conversion logic
+    // Generate the main part of the option conversion logic.
+    // Options are looked up by their localized name, because Discord sends only
+    // the options that were filled, so their positions shift when optional ones are skipped.
     let mut converter_code = quote! {
         let #option_ident = match &command
             .data
             .options
-            .get(#option_index) {
+            .iter()
+            .find(|option| option.name == loc!(&format!("{}-param-{}-name", #command_key, #option_key))
+                .chars()
+                .take(32)
+                .collect::<String>()) {
                 Some(option) => {
                     match &option.value {
                         serenity::model::application::CommandDataOptionValue::#option_type_ident(value) => Some(#prefix value #suffix),
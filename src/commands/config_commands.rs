@@ -43,6 +43,32 @@ pub async fn config_commands(ctx: &Context, guild: GuildId) {
             SHOPMANAGER.write().await.init().await;
         }
 
+        if path.clone().unwrap_or_default().contains("achievements/") {
+            achievement::ACHIEVEMENTMANAGER.write().await.init().await;
+        }
+
+        if path.clone().unwrap_or_default().contains("stat_templates/") {
+            if let Some(project) = file.filename.strip_suffix(".yml") {
+                if project::PROJECTMANAGER
+                    .read()
+                    .await
+                    .get(&project.to_string())
+                    .is_none()
+                {
+                    inter
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().content(loc!(
+                                "stat-template-unknown-project",
+                                "project" = project
+                            )),
+                        )
+                        .await
+                        .unwrap();
+                }
+            }
+        }
+
         Logger::high(
             fetch_member(&inter.user.id).await.unwrap().display_name(),
             &format!(
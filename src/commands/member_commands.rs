@@ -1,46 +1,94 @@
 use crate::{
     connect::{get_user_id, ConnectionError},
-    model::{member::MEMBERSMANAGER, role::ROLEMANAGER},
+    model::{
+        leaderboard::{Leaderboard, LeaderboardPost, LeaderboardScope, LEADERBOARDMANAGER},
+        member::MEMBERSMANAGER,
+        role::ROLEMANAGER,
+    },
     prelude::*,
 };
 use serenity::{
     self,
     all::{
-        ComponentInteractionDataKind, CreateActionRow, CreateSelectMenu, CreateSelectMenuOption,
+        ComponentInteractionDataKind, CreateActionRow, CreateAttachment,
+        CreateInteractionResponseFollowup, CreateSelectMenu, CreateSelectMenuOption,
     },
+    model::id::UserId,
 };
 
+async fn show_profile(
+    ctx: &Context,
+    inter: &CommandInteraction,
+    member: UserId,
+    show_secret: bool,
+) {
+    let view = member::ProfileView::new(member, show_secret);
+    let project_member = MEMBERSMANAGER
+        .write()
+        .await
+        .get(member)
+        .await
+        .unwrap()
+        .clone();
+    let (embed, pages) = project_member.profile_embed(ctx, &view).await;
+
+    inter
+        .edit_response(
+            &ctx.http,
+            EditInteractionResponse::new()
+                .embed(embed)
+                .components(view.components(pages)),
+        )
+        .await
+        .unwrap();
+
+    MEMBERSMANAGER
+        .write()
+        .await
+        .get_mut(inter.user.id)
+        .await
+        .unwrap()
+        .profile_view = Some(view);
+}
+
 pub async fn member_commands(ctx: &Context, guild: GuildId) {
     #[slash_command([])]
     async fn my_statistics(ctx: &Context, inter: CommandInteraction) {
-        let mut mem_man = MEMBERSMANAGER.write().await;
-        let member = mem_man.get(inter.user.id).await.unwrap();
-
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
-        inter
-            .edit_response(
-                &ctx.http,
-                EditInteractionResponse::new().embed(member.to_embed(&ctx, false).await),
-            )
-            .await
-            .unwrap();
+        show_profile(ctx, &inter, inter.user.id, false).await;
     }
 
     #[slash_command([])]
     async fn member_statistics(ctx: &Context, inter: CommandInteraction, dismember: User) {
-        let mut mem_man = MEMBERSMANAGER.write().await;
-        let member = mem_man.get(dismember.id).await.unwrap();
-
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
-        inter
-            .edit_response(
-                &ctx.http,
-                EditInteractionResponse::new().embed(member.to_embed(&ctx, true).await),
-            )
+        show_profile(ctx, &inter, dismember.id, true).await;
+
+        let member = MEMBERSMANAGER
+            .write()
             .await
-            .unwrap();
+            .get(dismember.id)
+            .await
+            .unwrap()
+            .clone();
+
+        if let Some((embed, chart)) = member.score_chart().await {
+            Logger::if_ok(
+                "commands.member_statistics",
+                "cannot send score chart",
+                inter
+                    .create_followup(
+                        &ctx.http,
+                        CreateInteractionResponseFollowup::new()
+                            .embed(embed)
+                            .add_file(chart)
+                            .ephemeral(true),
+                    )
+                    .await,
+            )
+            .await;
+        }
     }
 
     #[slash_command([])]
@@ -201,13 +249,178 @@ pub async fn member_commands(ctx: &Context, guild: GuildId) {
         inter
             .edit_response(
                 &ctx.http,
-                EditInteractionResponse::new()
-                    .content(format!("```{}```", get_user_id(ckey).await)),
+                EditInteractionResponse::new().content(format!(
+                    "```{}```",
+                    get_user_id(ckey).await.unwrap_or(String::from("Not Found"))
+                )),
             )
             .await
             .unwrap();
     }
 
+    #[slash_command([])]
+    async fn member_ckey(ctx: &Context, inter: CommandInteraction, mem: User, ckey: String) {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let link = member::CkeyLink::new(&ckey, Some(inter.user.id), true);
+
+        Logger::medium(
+            "commands.member_ckey",
+            &loc!(
+                "member-ckey-command-log-ckey-set",
+                "name" = mem.name,
+                "ckey" = link.ckey
+            ),
+        )
+        .await;
+
+        mem_man
+            .get_mut(mem.id)
+            .await
+            .unwrap()
+            .set_ckey(Some(link))
+            .await;
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("command-done-response"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn link_ckey(ctx: &Context, inter: CommandInteraction, ckey: String) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let mut link = member::CkeyLink::new(&ckey, None, false);
+
+        if link.ckey.is_empty() || get_user_id(link.ckey.clone()).await.is_none() {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("link-ckey-command-not-found", "ckey" = link.ckey)),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+
+        if mem_man
+            .get_by_ckey(&link.ckey)
+            .iter()
+            .any(|member| member.id != inter.user.id && member.ckey.as_ref().unwrap().verified)
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("link-ckey-command-taken", "ckey" = link.ckey)),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        if let Some(channel) = CONFIG.read().await.ckey_verify_channel {
+            match channel
+                .send_message(
+                    &ctx.http,
+                    CreateMessage::new()
+                        .content(loc!(
+                            "ckey-link-request",
+                            "member" = inter.user.id.get(),
+                            "ckey" = link.ckey
+                        ))
+                        .components(member::verify_buttons()),
+                )
+                .await
+            {
+                Ok(message) => link.message = Some(message.id),
+                Err(e) => {
+                    Logger::error(
+                        "commands.link_ckey",
+                        &format!("cannot send ckey verification request: {}", e),
+                    )
+                    .await;
+                }
+            }
+        }
+
+        let response = match link.message {
+            Some(_) => loc!("link-ckey-command-pending", "ckey" = link.ckey),
+            None => loc!("link-ckey-command-unverified", "ckey" = link.ckey),
+        };
+
+        mem_man
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .set_ckey(Some(link))
+            .await;
+        drop(mem_man);
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(response))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn ckey_lookup(
+        ctx: &Context,
+        inter: CommandInteraction,
+        member: Option<User>,
+        ckey: Option<String>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+
+        let embed = match (member, ckey) {
+            (Some(member), _) => {
+                let member = mem_man.get(member.id).await.unwrap().clone();
+                let ckey = member.ckey.as_ref().map(|link| link.ckey.clone());
+                drop(mem_man);
+
+                member::lookup_embed(Vec::from([&member]), ckey).await
+            }
+            (None, Some(ckey)) => {
+                let ckey = member::normalize_ckey(&ckey);
+                let members: Vec<member::ProjectMember> =
+                    mem_man.get_by_ckey(&ckey).into_iter().cloned().collect();
+                drop(mem_man);
+
+                member::lookup_embed(members.iter().collect(), Some(ckey)).await
+            }
+            (None, None) => {
+                drop(mem_man);
+
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new()
+                            .content(loc!("ckey-lookup-command-no-query")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+        };
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().embed(embed))
+            .await
+            .unwrap();
+    }
+
     #[slash_command([])]
     async fn note(ctx: &Context, inter: CommandInteraction, member: User, text: String) {
         let mut mem_man = member::MEMBERSMANAGER.write().await;
@@ -234,6 +447,7 @@ pub async fn member_commands(ctx: &Context, guild: GuildId) {
         let mem = mem_man.get_mut(member.id).await.unwrap();
 
         mem.add_warn(inter.user.id, text).await;
+        drop(mem_man);
 
         inter
             .create_response(
@@ -246,6 +460,204 @@ pub async fn member_commands(ctx: &Context, guild: GuildId) {
             )
             .await
             .unwrap();
+
+        member::escalate_warns(ctx, member.id).await;
+    }
+
+    #[slash_command([])]
+    async fn warn_appeal(ctx: &Context, inter: CommandInteraction) {
+        let policy = CONFIG.read().await.warn_policy.clone();
+
+        if policy.appeal_channel.is_none() {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("warn-appeal-no-channel"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let member = mem_man.get(inter.user.id).await.unwrap();
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(match member::appeal_select(member, &policy) {
+                    Some(select) => CreateInteractionResponseMessage::new()
+                        .content(loc!("warn-appeal-select-content"))
+                        .components(Vec::from([select]))
+                        .ephemeral(true),
+                    None => CreateInteractionResponseMessage::new()
+                        .content(loc!("warn-appeal-no-warns"))
+                        .ephemeral(true),
+                }),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([
+        amount = [min_int_value = 1],
+        reason = [max_length = 200],
+    ])]
+    async fn give_score(
+        ctx: &Context,
+        inter: CommandInteraction,
+        member: User,
+        amount: i64,
+        reason: String,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let policy = CONFIG.read().await.score_transfer.clone();
+        let dis_sender = fetch_member(&inter.user.id).await.unwrap();
+        let Ok(receiver) = fetch_member(&member.id).await else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("give-score-member-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let content =
+            match member::give_score(&dis_sender, &receiver, amount, reason, &policy).await {
+                Ok(member::TransferState::Done) => loc!(
+                    "give-score-command-done",
+                    "member" = member.id.get(),
+                    "amount" = amount
+                ),
+                Ok(_) => loc!(
+                    "give-score-command-pending",
+                    "member" = member.id.get(),
+                    "amount" = amount
+                ),
+                Err(e) => e.describe(),
+            };
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([
+        format = [choice = locale],
+    ])]
+    async fn member_export(ctx: &Context, inter: CommandInteraction, format: Option<String>) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let member = MEMBERSMANAGER
+            .write()
+            .await
+            .get(inter.user.id)
+            .await
+            .unwrap()
+            .clone();
+        let export = member::MemberExport::new(&member).await;
+
+        let (content, file_name) = match format.as_deref() {
+            Some("member-export-format-markdown") => (
+                export.to_markdown(),
+                format!("member-{}.md", member.id.get()),
+            ),
+            _ => (export.to_json(), format!("member-{}.json", member.id.get())),
+        };
+
+        Logger::low(
+            "commands.member_export",
+            &format!("member {} exported own data", member.id.get()),
+        )
+        .await;
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .new_attachment(CreateAttachment::bytes(content, file_name)),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([
+        mode = [choice = locale],
+    ])]
+    async fn member_erase(ctx: &Context, inter: CommandInteraction, member: User, mode: String) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let delete = mode == "member-erase-mode-delete";
+        let tasks = member::erase_member(inter.user.id, member.id, delete).await;
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().content(loc!(
+                    match delete {
+                        true => "member-erase-command-deleted",
+                        false => "member-erase-command-anonymized",
+                    },
+                    "member" = member.id.get(),
+                    "tasks" = tasks
+                )),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn member_merge(
+        ctx: &Context,
+        inter: CommandInteraction,
+        old_member: User,
+        new_member: User,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if old_member.id == new_member.id {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("member-merge-command-same-member")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let merge = member::MemberMerge {
+            from: old_member.id,
+            into: new_member.id,
+        };
+        let embed = merge.preview_embed().await;
+
+        MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .member_merge = Some(merge);
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(member::MemberMerge::confirm_buttons()),
+            )
+            .await
+            .unwrap();
     }
 
     #[listen_component("link-folder:db")]
@@ -306,4 +718,144 @@ pub async fn member_commands(ctx: &Context, guild: GuildId) {
             };
         }
     }
+
+    #[slash_command([
+        scope = [choice = locale],
+    ])]
+    async fn leaderboard(
+        ctx: &Context,
+        inter: CommandInteraction,
+        scope: Option<String>,
+        project_name: Option<String>,
+        days: Option<i64>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if let Some(name) = &project_name {
+            if project::PROJECTMANAGER.read().await.get(name).is_none() {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("project-not-found")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+        }
+
+        let board = Leaderboard::new(
+            LeaderboardScope::from_choice(scope.as_deref().unwrap_or_default()),
+            project_name,
+            days,
+        );
+        let rows = board.rows().await;
+        let embed = board.to_embed(&rows, 0, Some(inter.user.id));
+
+        MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .leaderboard_view = Some((board, 0));
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(Leaderboard::page_buttons()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([
+        scope = [choice = locale],
+    ])]
+    async fn leaderboard_post(
+        ctx: &Context,
+        inter: CommandInteraction,
+        scope: Option<String>,
+        project_name: Option<String>,
+        days: Option<i64>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let Some(channel) = CONFIG.read().await.leaderboard_channel else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("leaderboard-post-command-no-channel")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        if let Some(name) = &project_name {
+            if project::PROJECTMANAGER.read().await.get(name).is_none() {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("project-not-found")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+        }
+
+        let board = Leaderboard::new(
+            LeaderboardScope::from_choice(scope.as_deref().unwrap_or_default()),
+            project_name,
+            days,
+        );
+        let rows = board.rows().await;
+
+        match channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new().embed(board.to_embed(&rows, 0, None)),
+            )
+            .await
+        {
+            Ok(message) => {
+                LEADERBOARDMANAGER
+                    .write()
+                    .await
+                    .add_post(LeaderboardPost {
+                        channel,
+                        message: message.id,
+                        board,
+                    })
+                    .await;
+
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("command-done-response")),
+                    )
+                    .await
+                    .unwrap();
+            }
+            Err(e) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new()
+                            .content(loc!("leaderboard-post-command-error")),
+                    )
+                    .await
+                    .unwrap();
+                Logger::error(
+                    "commands.leaderboard_post",
+                    &format!("cannot send leaderboard post: {}", e),
+                )
+                .await;
+            }
+        }
+    }
 }
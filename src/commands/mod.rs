@@ -5,6 +5,7 @@ mod member_commands;
 mod project_commands;
 mod role_commands;
 mod save_commands;
+mod season_commands;
 mod shop_commands;
 mod tag_commands;
 mod task_commands;
@@ -16,6 +17,7 @@ pub use member_commands::*;
 pub use project_commands::*;
 pub use role_commands::*;
 pub use save_commands::*;
+pub use season_commands::*;
 pub use shop_commands::*;
 pub use tag_commands::*;
 pub use task_commands::*;
@@ -16,6 +16,17 @@ pub async fn project_commands(ctx: &Context, guild: GuildId) {
         stat_channel: Option<PartialChannel>,
     ) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_global(&project::Caller::from_command(&inter)).await {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
         let mut proj_man = PROJECTMANAGER.try_write().unwrap();
 
         match proj_man
@@ -64,6 +75,23 @@ pub async fn project_commands(ctx: &Context, guild: GuildId) {
     #[slash_command([])]
     async fn change_project(ctx: &Context, inter: CommandInteraction, project_name: String) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
         let mut mem_man = member::MEMBERSMANAGER.write().await;
         let proj_man = project::PROJECTMANAGER.read().await;
 
@@ -106,6 +134,23 @@ pub async fn project_commands(ctx: &Context, guild: GuildId) {
     async fn project_config(ctx: &Context, inter: CommandInteraction, project_name: String) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
         let proj_man = project::PROJECTMANAGER.read().await;
 
         if let Some(project) = proj_man.get(&project_name) {
@@ -131,6 +176,17 @@ pub async fn project_commands(ctx: &Context, guild: GuildId) {
     async fn delete_project(ctx: &Context, inter: CommandInteraction, project_name: String) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
+        if !project::check_global(&project::Caller::from_command(&inter)).await {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
         let mut proj_man = project::PROJECTMANAGER.write().await;
         if let Some(_) = proj_man.delete(&project_name).await {
             inter
@@ -150,4 +206,372 @@ pub async fn project_commands(ctx: &Context, guild: GuildId) {
                 .unwrap();
         }
     }
+
+    #[slash_command([new_name=[max_length=100]])]
+    async fn rename_project(
+        ctx: &Context,
+        inter: CommandInteraction,
+        project_name: String,
+        new_name: String,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let content = match project::rename_project(&project_name, &new_name).await {
+            Ok((renamed, configs)) => {
+                let mut content = loc!(
+                    "command-rename-project-done",
+                    "project" = new_name,
+                    "count" = renamed
+                );
+                if !configs.is_empty() {
+                    content = format!(
+                        "{}\n{}",
+                        content,
+                        loc!(
+                            "command-rename-project-configs",
+                            "project" = project_name,
+                            "configs" = configs.join(", ")
+                        )
+                    );
+                }
+                content
+            }
+            Err(e) => {
+                Logger::error(
+                    "commands.rename_project",
+                    &format!("cannot rename project, {}", e),
+                )
+                .await;
+                loc!("command-rename-project-error")
+            }
+        };
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn archive_project(ctx: &Context, inter: CommandInteraction, project_name: String) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let content = match proj_man.get_mut(&project_name) {
+            Some(project) => match project.archive(ctx, inter.user.id).await {
+                Ok(_) => loc!("command-done-response"),
+                Err(e) => {
+                    Logger::error(
+                        "commands.archive_project",
+                        &format!("cannot archive project, {}", e),
+                    )
+                    .await;
+                    loc!("command-archive-project-error")
+                }
+            },
+            None => loc!("project-not-found"),
+        };
+        drop(proj_man);
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn restore_project(ctx: &Context, inter: CommandInteraction, project_name: String) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let content = match proj_man.get_mut(&project_name) {
+            Some(project) => match project.restore(ctx, inter.user.id).await {
+                Ok(_) => loc!("command-done-response"),
+                Err(e) => {
+                    Logger::error(
+                        "commands.restore_project",
+                        &format!("cannot restore project, {}", e),
+                    )
+                    .await;
+                    loc!("command-restore-project-error")
+                }
+            },
+            None => loc!("project-not-found"),
+        };
+        drop(proj_man);
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([default_tags=[max_length=200]])]
+    async fn add_project_forum(
+        ctx: &Context,
+        inter: CommandInteraction,
+        project_name: String,
+        forum: PartialChannel,
+        waiter_role: Option<Role>,
+        default_tags: Option<String>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut task_forum = project::TaskForum::new(forum.id);
+        task_forum.waiter_role = waiter_role.map(|role| role.id);
+
+        if let Some(names) = default_tags {
+            let available = match fetch_channel(ctx, forum.id) {
+                Ok(channel) => channel.available_tags,
+                Err(_) => Vec::new(),
+            };
+
+            for name in names.split(',').map(|x| x.trim()).filter(|x| !x.is_empty()) {
+                match available.iter().find(|tag| tag.name == name) {
+                    Some(tag) => task_forum.default_tags.push(tag.id),
+                    None => {
+                        inter
+                            .edit_response(
+                                &ctx.http,
+                                EditInteractionResponse::new().content(loc!(
+                                    "command-add-project-forum-tag-not-found",
+                                    "tag" = name
+                                )),
+                            )
+                            .await
+                            .unwrap();
+                        return;
+                    }
+                }
+            }
+        }
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+
+        if let Some(other) = proj_man.get_from_forum(&forum.id) {
+            if other.name() != &project_name {
+                let content = loc!("command-add-project-forum-used", "project" = other.name());
+                drop(proj_man);
+
+                inter
+                    .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+                    .await
+                    .unwrap();
+                return;
+            }
+        }
+
+        let content = match proj_man.get_mut(&project_name) {
+            Some(project) => {
+                project.set_forum(task_forum).await;
+                loc!("command-done-response")
+            }
+            None => loc!("project-not-found"),
+        };
+        drop(proj_man);
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn remove_project_forum(
+        ctx: &Context,
+        inter: CommandInteraction,
+        project_name: String,
+        forum: PartialChannel,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_project(
+            &project::Caller::from_command(&inter),
+            &project_name,
+            project::ProjectPermission::ProjectConfig,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let content = match proj_man.get_mut(&project_name) {
+            Some(project) => match project.remove_forum(forum.id).await {
+                true => loc!("command-done-response"),
+                false => loc!("command-remove-project-forum-not-found"),
+            },
+            None => loc!("project-not-found"),
+        };
+        drop(proj_man);
+
+        inter
+            .edit_response(&ctx.http, EditInteractionResponse::new().content(content))
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn project_managers(
+        ctx: &Context,
+        inter: CommandInteraction,
+        project_name: String,
+        role: Role,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if !project::check_global(&project::Caller::from_command(&inter)).await {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let Some(select) = project::PROJECTMANAGER
+            .read()
+            .await
+            .get(&project_name)
+            .map(|project| project.manager_select(role.id))
+        else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut mem_man = member::MEMBERSMANAGER.write().await;
+        let member = mem_man.get_mut(inter.user.id).await.unwrap();
+        member.changed_project = Some(project_name.clone());
+        member.changed_role = Some(role.id);
+        drop(mem_man);
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .content(loc!(
+                        "command-project-managers-select",
+                        "project" = project_name,
+                        "role" = role.id.get()
+                    ))
+                    .components(Vec::from([select])),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn project_stats(ctx: &Context, inter: CommandInteraction, project_name: String) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let Some(project) = project::PROJECTMANAGER
+            .read()
+            .await
+            .get(&project_name)
+            .cloned()
+        else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut embeds = Vec::from([project.stats_embed().await]);
+        let (charts, files) = project::project_charts(ctx, &project).await;
+        embeds.extend(charts);
+
+        let mut response = EditInteractionResponse::new().embeds(embeds);
+        for file in files {
+            response = response.new_attachment(file);
+        }
+
+        inter.edit_response(&ctx.http, response).await.unwrap();
+    }
 }
@@ -1,7 +1,7 @@
 use crate::{
     config::load_env,
     connect::*,
-    model::{member::MEMBERSMANAGER, role::ROLEMANAGER},
+    model::{member::MEMBERSMANAGER, role::ROLEMANAGER, task::LastSave},
     prelude::*,
 };
 use serenity::{
@@ -14,11 +14,105 @@ use serenity::{
 
 pub async fn save_commands(ctx: &Context, guild: GuildId) {
     #[slash_command([])]
-    async fn save(ctx: &Context, inter: CommandInteraction, path: Option<String>) {
+    async fn save(
+        ctx: &Context,
+        inter: CommandInteraction,
+        path: Option<String>,
+        last_save: Option<bool>,
+    ) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
         let dis_member = fetch_member(&inter.user.id).await.unwrap();
 
+        if last_save.unwrap_or(false) {
+            let save = match task::TASKMANAGER.read().await.get_thread(inter.channel_id) {
+                Some(task) => match task.last_save.get() {
+                    Some(LastSave::Current(save)) => save.clone(),
+                    _ => {
+                        inter
+                            .edit_response(
+                                &ctx.http,
+                                EditInteractionResponse::new()
+                                    .content(loc!("save-command-no-last-save")),
+                            )
+                            .await
+                            .unwrap();
+                        return;
+                    }
+                },
+                None => {
+                    inter
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new()
+                                .content(loc!("task-command-not-in-task")),
+                        )
+                        .await
+                        .unwrap();
+                    return;
+                }
+            };
+
+            if !ROLEMANAGER
+                .read()
+                .await
+                .have_db_permission(&dis_member, &save.db)
+            {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("link-folder-command-no-dbs")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+
+            match unload_content(save.path(), save.db.clone()).await {
+                Ok(data) => {
+                    inter
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().new_attachment(CreateAttachment::bytes(
+                                data,
+                                save.file.split("/").last().unwrap_or("unknown.yml"),
+                            )),
+                        )
+                        .await
+                        .unwrap();
+
+                    Logger::low(
+                        "commands.save",
+                        &format!(
+                            "unloaded last save {} from {} by {} ({})",
+                            save.path(),
+                            save.db,
+                            inter.user.display_name(),
+                            inter.user.id.get()
+                        ),
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    Logger::error(
+                        "commands.save",
+                        &format!("error while unloading last save: {:?}", e),
+                    )
+                    .await;
+
+                    inter
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new().content(loc!("save-unload-error")),
+                        )
+                        .await
+                        .unwrap();
+                }
+            }
+
+            return;
+        }
+
         let role_man = ROLEMANAGER.read().await;
         let mut mem_man = member::MEMBERSMANAGER.write().await;
 
@@ -0,0 +1,116 @@
+use crate::{
+    model::{
+        member::MEMBERSMANAGER,
+        season::{SeasonEnd, SEASONMANAGER},
+    },
+    prelude::*,
+};
+
+pub async fn season_commands(ctx: &Context, guild: GuildId) {
+    #[slash_command([])]
+    async fn season_end(
+        ctx: &Context,
+        inter: CommandInteraction,
+        name: Option<String>,
+        next_name: Option<String>,
+        decay: Option<f64>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let season_man = SEASONMANAGER.read().await;
+
+        let Some(name) = name.or(season_man.current().name.clone()) else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("season-end-command-no-name")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        if season_man.get_by_name(&name).is_some() {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("season-end-command-name-exists")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+        drop(season_man);
+
+        let decay = decay
+            .unwrap_or(CONFIG.read().await.season_score_decay)
+            .clamp(0.0, 1.0);
+
+        let end = SeasonEnd {
+            name,
+            next_name,
+            decay,
+        };
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let members = mem_man
+            .get_all()
+            .iter()
+            .filter(|member| member.total_score() != 0)
+            .count();
+        let embed = end.to_embed(members);
+
+        mem_man.get_mut(inter.user.id).await.unwrap().season_end = Some(end);
+        drop(mem_man);
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(SeasonEnd::confirm_buttons()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn season_results(ctx: &Context, inter: CommandInteraction, season: Option<String>) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let season_man = SEASONMANAGER.read().await;
+
+        let found = match &season {
+            Some(name) => season_man.get_by_name(name),
+            None => season_man.latest(),
+        };
+
+        let Some(found) = found else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(match season {
+                        Some(_) => loc!("season-results-command-not-found"),
+                        None => loc!("season-results-command-no-seasons"),
+                    }),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let others = season_man
+            .names()
+            .into_iter()
+            .filter(|name| name != &&found.name)
+            .collect();
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(found.to_embed(others)),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -53,6 +53,23 @@ pub async fn tag_commands(ctx: &Context, guild: GuildId) {
     ) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
+        if !project::check_forum(
+            &project::Caller::from_command(&inter),
+            channel.id,
+            project::ProjectPermission::TagChange,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
         let mut mem_man = member::MEMBERSMANAGER.write().await;
         let tag_man = tag::TAGSMANAGER.read().await;
         let channel = fetch_channel(&ctx, channel.id).unwrap();
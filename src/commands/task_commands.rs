@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 
-use crate::prelude::*;
+use crate::{
+    connect::{find_save, latest_save, ConnectionError, SaveFile},
+    model::task::{BulkAction, BulkOperation, LastSave, TaskFilter, TaskState},
+    prelude::*,
+};
 use serenity::{
     self,
-    all::{Colour, CreateEmbed},
+    all::{Colour, CreateAttachment, CreateEmbed},
 };
 
 pub async fn task_commands(ctx: &Context, guild: GuildId) {
@@ -39,38 +43,127 @@ pub async fn task_commands(ctx: &Context, guild: GuildId) {
     }
 
     #[slash_command([])]
-    async fn last_save(ctx: &Context, inter: CommandInteraction, path: Option<String>) {
-        let mut task_man = task::TASKMANAGER.write().await;
+    async fn last_save(
+        ctx: &Context,
+        inter: CommandInteraction,
+        path: Option<String>,
+        latest: Option<bool>,
+    ) {
+        inter.defer(&ctx.http).await.unwrap();
 
-        if let Some(task) = task_man.get_thread_mut(inter.channel_id) {
-            if let Some(save) = path {
-                task.set_last_save(&ctx, Some(save)).await;
+        let candidates = match task::TASKMANAGER.read().await.get_thread(inter.channel_id) {
+            Some(task) => task.save_candidates(),
+            None => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("task-command-not-in-task")),
+                    )
+                    .await
+                    .unwrap();
+                return;
             }
+        };
 
-            inter
-                .create_response(
-                    &ctx.http,
-                    CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new().content(loc!(
-                            "last-save-command-message",
-                            "last_save" = match task.last_save.get() {
-                                Some(task_save) => task_save.clone(),
-                                None => loc!("task-no-last-save"),
-                            }
-                        )),
-                    ),
-                )
+        let latest = latest.unwrap_or(false);
+
+        if path.is_some() || latest {
+            let mut folders = Vec::new();
+            let mut mem_man = member::MEMBERSMANAGER.write().await;
+
+            for id in candidates {
+                if let Ok(member) = mem_man.get(id).await {
+                    for (db, folder) in member.own_folder.iter() {
+                        if let Some(folder) = folder {
+                            folders.push((db.clone(), folder.clone()));
+                        }
+                    }
+                }
+            }
+            drop(mem_man);
+
+            let mut found: Option<SaveFile> = None;
+
+            for (db, folder) in folders {
+                let result = match &path {
+                    Some(p) if !latest => find_save(folder, p.clone(), db).await,
+                    _ => latest_save(folder, db).await,
+                };
+
+                match result {
+                    Ok(Some(save)) => {
+                        if !latest {
+                            found = Some(save);
+                            break;
+                        }
+
+                        let is_newer = match &found {
+                            Some(current) => current.timestamp() < save.timestamp(),
+                            None => true,
+                        };
+
+                        if is_newer {
+                            found = Some(save);
+                        }
+                    }
+                    Ok(None) => (),
+                    Err(ConnectionError::NotAllowedUrl(_)) => {
+                        inter
+                            .edit_response(
+                                &ctx.http,
+                                EditInteractionResponse::new().content(loc!("not-allowed-url")),
+                            )
+                            .await
+                            .unwrap();
+                        return;
+                    }
+                    Err(e) => {
+                        Logger::debug(
+                            "commands.last_save",
+                            &format!("cannot look up saves in linked folder: {:?}", e),
+                        )
+                        .await;
+                    }
+                }
+            }
+
+            let Some(save) = found else {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(match latest {
+                            true => loc!("last-save-command-no-saves"),
+                            false => loc!("last-save-command-not-found"),
+                        }),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            };
+
+            if let Some(task) = task::TASKMANAGER
+                .write()
                 .await
-                .unwrap();
-        } else {
+                .get_thread_mut(inter.channel_id)
+            {
+                task.set_last_save(&ctx, Some(LastSave::Current(save)))
+                    .await;
+            }
+        }
+
+        let task_man = task::TASKMANAGER.read().await;
+
+        if let Some(task) = task_man.get_thread(inter.channel_id) {
             inter
-                .create_response(
+                .edit_response(
                     &ctx.http,
-                    CreateInteractionResponse::Message(
-                        CreateInteractionResponseMessage::new()
-                            .content(loc!("task-command-not-in-task"))
-                            .ephemeral(true),
-                    ),
+                    EditInteractionResponse::new().content(loc!(
+                        "last-save-command-message",
+                        "last_save" = match task.last_save.get() {
+                            Some(task_save) => task_save.to_string(),
+                            None => loc!("task-no-last-save"),
+                        }
+                    )),
                 )
                 .await
                 .unwrap();
@@ -140,6 +233,23 @@ pub async fn task_commands(ctx: &Context, guild: GuildId) {
     async fn task_change(ctx: &Context, inter: CommandInteraction) {
         inter.defer_ephemeral(&ctx.http).await.unwrap();
 
+        if !project::check_task(
+            &project::Caller::from_command(&inter),
+            inter.channel_id,
+            project::ProjectPermission::TaskChange,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
         let task_man = task::TASKMANAGER.read().await;
 
         if let Some(task) = task_man.get_thread(inter.channel_id) {
@@ -176,6 +286,27 @@ pub async fn task_commands(ctx: &Context, guild: GuildId) {
 
     #[slash_command([])]
     async fn task_close(ctx: &Context, inter: CommandInteraction) {
+        if !project::check_task(
+            &project::Caller::from_command(&inter),
+            inter.channel_id,
+            project::ProjectPermission::TaskClose,
+        )
+        .await
+        {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("project-access-denied"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
         let mut mem_man = member::MEMBERSMANAGER.write().await;
         let member = mem_man.get_mut(inter.user.id).await.unwrap();
         let mut task_man = task::TASKMANAGER.write().await;
@@ -266,4 +397,369 @@ pub async fn task_commands(ctx: &Context, guild: GuildId) {
                 .unwrap();
         }
     }
+
+    #[slash_command([
+        format = [choice = locale],
+    ])]
+    async fn task_transcript(
+        ctx: &Context,
+        inter: CommandInteraction,
+        task_id: Option<i64>,
+        format: Option<String>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let task_man = task::TASKMANAGER.read().await;
+        let task = match task_id {
+            Some(id) => task_man.get(id as u32),
+            None => task_man.get_thread(inter.channel_id),
+        };
+
+        let Some(task) = task else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("task-transcript-command-task-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let (id, name, thread_id) = (task.id, task.name.get().clone(), task.thread_id);
+        drop(task_man);
+
+        if !project::check_task(
+            &project::Caller::from_command(&inter),
+            thread_id,
+            project::ProjectPermission::TaskChange,
+        )
+        .await
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-access-denied")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let transcript = match task::TaskTranscript::load(id) {
+            Some(transcript) => transcript,
+            None => match task::TaskTranscript::collect(&ctx.http, id, name, thread_id).await {
+                Ok(transcript) => transcript,
+                Err(e) => {
+                    Logger::debug(
+                        "commands.task_transcript",
+                        &format!("cannot collect thread history of task {}: {}", id, e),
+                    )
+                    .await;
+
+                    inter
+                        .edit_response(
+                            &ctx.http,
+                            EditInteractionResponse::new()
+                                .content(loc!("task-transcript-command-collect-error")),
+                        )
+                        .await
+                        .unwrap();
+                    return;
+                }
+            },
+        };
+
+        let (content, file_name) = match format.as_deref() {
+            Some("task-transcript-format-html") => {
+                (transcript.to_html(), format!("task-{}.html", id))
+            }
+            _ => (transcript.to_markdown(), format!("task-{}.md", id)),
+        };
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .new_attachment(CreateAttachment::bytes(content, file_name)),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[slash_command([])]
+    async fn task_move(
+        ctx: &Context,
+        inter: CommandInteraction,
+        project_name: String,
+        task_id: Option<i64>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let Some(project) = project::PROJECTMANAGER
+            .read()
+            .await
+            .get(&project_name)
+            .cloned()
+        else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut task_man = task::TASKMANAGER.write().await;
+        let task = match task_id {
+            Some(id) => task_man.get(id as u32),
+            None => task_man.get_thread(inter.channel_id),
+        };
+
+        let Some(task) = task else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("task-move-command-task-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        if task.finished {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("task-move-command-finished")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        if &task.project == project.name() {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("task-move-command-same-project")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let id = task.id;
+
+        match task_man.move_task(ctx, id, &project).await {
+            Ok(thread) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new()
+                            .content(loc!("task-move-command-done", "thread" = thread.get())),
+                    )
+                    .await
+                    .unwrap();
+            }
+            Err(e) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("task-move-command-error")),
+                    )
+                    .await
+                    .unwrap();
+                Logger::error(
+                    "commands.task_move",
+                    &format!("cannot move task {}: {}", id, e),
+                )
+                .await;
+            }
+        }
+    }
+
+    #[slash_command([])]
+    async fn task_clone(
+        ctx: &Context,
+        inter: CommandInteraction,
+        task_id: Option<i64>,
+        project_name: Option<String>,
+        name: Option<String>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let mut task_man = task::TASKMANAGER.write().await;
+        let task = match task_id {
+            Some(id) => task_man.get(id as u32),
+            None => task_man.get_thread(inter.channel_id),
+        };
+
+        let Some(task) = task else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("task-clone-command-task-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let id = task.id;
+        let project_name = project_name.unwrap_or(task.project.clone());
+
+        let Some(project) = project::PROJECTMANAGER
+            .read()
+            .await
+            .get(&project_name)
+            .cloned()
+        else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("project-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        match task_man.clone_task(ctx, id, &project, name).await {
+            Ok(new_id) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!(
+                            "task-clone-command-done",
+                            "thread" = task_man.get(new_id).unwrap().thread_id.get()
+                        )),
+                    )
+                    .await
+                    .unwrap();
+            }
+            Err(e) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("task-clone-command-error")),
+                    )
+                    .await
+                    .unwrap();
+                Logger::error(
+                    "commands.task_clone",
+                    &format!("cannot clone task {}: {}", id, e),
+                )
+                .await;
+            }
+        }
+    }
+
+    #[slash_command([
+        action = [choice = locale],
+        state = [choice = locale],
+    ])]
+    #[allow(clippy::too_many_arguments)]
+    async fn tasks_bulk(
+        ctx: &Context,
+        inter: CommandInteraction,
+        action: String,
+        project_name: Option<String>,
+        tag_name: Option<String>,
+        state: Option<String>,
+        member: Option<User>,
+        older_than_days: Option<i64>,
+        user: Option<User>,
+        score: Option<i64>,
+    ) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        if let Some(name) = &project_name {
+            if project::PROJECTMANAGER.read().await.get(name).is_none() {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("project-not-found")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+        }
+
+        let action = match action.as_str() {
+            "tasks-bulk-action-close" => Some(BulkAction::Close),
+            "tasks-bulk-action-reopen" => Some(BulkAction::Reopen),
+            "tasks-bulk-action-set-mentor" => Some(BulkAction::SetMentor(user.map(|x| x.id))),
+            "tasks-bulk-action-set-score" => score.map(BulkAction::SetScore),
+            "tasks-bulk-action-remove-member" => user
+                .as_ref()
+                .or(member.as_ref())
+                .map(|x| BulkAction::RemoveMember(x.id)),
+            "tasks-bulk-action-fetch-tags" => Some(BulkAction::FetchTags),
+            _ => None,
+        };
+
+        let Some(action) = action else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new()
+                        .content(loc!("tasks-bulk-command-missing-argument")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let filter = TaskFilter {
+            project: project_name,
+            tag: tag_name,
+            state: match state.as_deref() {
+                Some("tasks-bulk-state-open") => TaskState::Open,
+                Some("tasks-bulk-state-finished") => TaskState::Finished,
+                _ => TaskState::Any,
+            },
+            member: member.map(|x| x.id),
+            older_than_days,
+        };
+
+        let operation = BulkOperation::new(ctx, filter, action).await;
+
+        if operation.tasks.is_empty() {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("tasks-bulk-command-no-tasks")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let embed = operation.preview_embed().await;
+
+        member::MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .bulk_operation = Some(operation);
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(embed)
+                    .components(BulkOperation::confirm_buttons()),
+            )
+            .await
+            .unwrap();
+    }
 }
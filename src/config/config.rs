@@ -1,5 +1,6 @@
 use crate::localization::LocalizationData;
 use crate::logger::LoggingConfig;
+use crate::model::member::{OnboardingConfig, TransferPolicy, WarnPolicy};
 use dotenv;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
@@ -26,6 +27,10 @@ pub static CONFIG: Lazy<Arc<RwLock<Config>>> = Lazy::new(|| {
     Arc::new(RwLock::new(Config::new("config.toml")))
 });
 
+fn default_stat_update_debounce() -> u64 {
+    15
+}
+
 pub fn load_env() {
     dotenv::from_path(DATA_PATH.join(".env")).expect("Cannot load .env");
 }
@@ -35,12 +40,19 @@ pub struct Config {
     pub guild: u64,
     pub max_dropdowns_per_message: u64,
     pub project_stat_update_duration: u64,
+    #[serde(default = "default_stat_update_debounce")]
+    pub stat_update_debounce: u64,
     pub brigadire_score_modifier: f64,
     pub userid_api_url: String,
     pub notify_on: Option<(String, ChannelId)>,
     pub log: Option<ChannelId>,
     pub logging_template: String,
     pub guest_role: Option<RoleId>,
+    pub leaderboard_channel: Option<ChannelId>,
+    pub achievement_channel: Option<ChannelId>,
+    pub ckey_verify_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub season_score_decay: f64,
     #[serde(rename = "Localization")]
     pub localization: LocalizationData,
     #[serde(rename = "TaskRatings")]
@@ -49,6 +61,12 @@ pub struct Config {
     pub commands: HashMap<String, bool>,
     #[serde(rename = "Logging")]
     pub logging: LoggingConfig,
+    #[serde(rename = "WarnPolicy", default)]
+    pub warn_policy: WarnPolicy,
+    #[serde(rename = "ScoreTransfer", default)]
+    pub score_transfer: TransferPolicy,
+    #[serde(rename = "Onboarding", default)]
+    pub onboarding: OnboardingConfig,
 }
 
 impl Config {
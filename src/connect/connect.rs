@@ -1,7 +1,11 @@
 use crate::config::{write_file, CONFIG, DATA_PATH};
+use chrono::NaiveDateTime;
+use once_cell::sync::Lazy;
 use reqwest::Error;
 use scraper::{Html, Selector};
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, env, path::PathBuf};
+use tokio::sync::RwLock;
 
 #[derive(Debug)]
 pub enum ConnectionError {
@@ -74,31 +78,42 @@ pub async fn unload_to_file(url: String, db_name: String) -> Result<PathBuf, Con
     Ok(path)
 }
 
-pub async fn get_user_id(name: String) -> String {
-    let config = CONFIG.read().await;
-    let not_found = "Not Found".to_string();
+static USERID_CACHE: Lazy<RwLock<HashMap<String, String>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
 
-    let url = format!("{}name={}", config.userid_api_url, name);
-    let data = match reqwest::Client::new().get(url).send().await {
-        Ok(content) => match content.text().await {
-            Ok(text) => text,
-            Err(_) => return not_found,
-        },
-        Err(_) => return not_found,
-    }
-    .replace("{", "")
-    .replace("}", "")
-    .replace("\"", "");
-
-    for i in data.split(",") {
-        if i.contains("userId") {
-            match i.split(":").last() {
-                Some(string) => return string.to_string(),
-                None => return not_found,
-            };
-        }
+#[derive(Deserialize)]
+struct UserIdResponse {
+    #[serde(rename = "userId")]
+    user_id: Option<serde_json::Value>,
+}
+
+pub async fn get_user_id(name: String) -> Option<String> {
+    let name = name.to_lowercase();
+
+    if let Some(user_id) = USERID_CACHE.read().await.get(&name) {
+        return Some(user_id.clone());
     }
-    not_found
+
+    let url = format!("{}name={}", CONFIG.read().await.userid_api_url, name);
+    let text = reqwest::Client::new()
+        .get(url)
+        .send()
+        .await
+        .ok()?
+        .text()
+        .await
+        .ok()?;
+    let response: UserIdResponse = serde_json::from_str(&text).ok()?;
+
+    let user_id = match response.user_id? {
+        serde_json::Value::String(string) => string,
+        serde_json::Value::Number(number) => number.to_string(),
+        _ => return None,
+    };
+
+    USERID_CACHE.write().await.insert(name, user_id.clone());
+
+    Some(user_id)
 }
 
 pub async fn file_dates(
@@ -126,3 +141,87 @@ pub async fn file_dates(
 
     Ok(result)
 }
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub struct SaveFile {
+    pub db: String,
+    pub folder: String,
+    pub file: String,
+    pub date: String,
+}
+
+impl SaveFile {
+    pub fn path(&self) -> String {
+        format!("{}/{}", self.folder, self.file)
+    }
+
+    pub fn timestamp(&self) -> Option<NaiveDateTime> {
+        parse_file_date(&self.date)
+    }
+}
+
+pub fn parse_file_date(date: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(date.trim(), "%d-%b-%Y %H:%M").ok()
+}
+
+pub async fn find_save(
+    folder: String,
+    path: String,
+    db_name: String,
+) -> Result<Option<SaveFile>, ConnectionError> {
+    let path = path.trim().trim_start_matches("/").to_string();
+
+    if path.is_empty() || path.ends_with("/") {
+        return Ok(None);
+    }
+
+    let (dir, file_name) = match path.rsplit_once("/") {
+        Some((dir, file_name)) => (format!("{}/{}/", folder, dir), file_name.to_string()),
+        None => (format!("{}/", folder), path.clone()),
+    };
+
+    Ok(file_dates(dir, db_name.clone())
+        .await?
+        .remove(&file_name)
+        .map(|date| SaveFile {
+            db: db_name,
+            folder,
+            file: path,
+            date,
+        }))
+}
+
+pub async fn latest_save(
+    folder: String,
+    db_name: String,
+) -> Result<Option<SaveFile>, ConnectionError> {
+    let mut latest: Option<SaveFile> = None;
+
+    for (file_name, date) in file_dates(format!("{}/", folder), db_name.clone()).await? {
+        if file_name.ends_with("/") {
+            continue;
+        }
+
+        let save = SaveFile {
+            db: db_name.clone(),
+            folder: folder.clone(),
+            file: file_name,
+            date,
+        };
+
+        if save.timestamp().is_none() {
+            continue;
+        }
+
+        let is_newer = match &latest {
+            Some(current) => current.timestamp() < save.timestamp(),
+            None => true,
+        };
+
+        if is_newer {
+            latest = Some(save);
+        }
+    }
+
+    Ok(latest)
+}
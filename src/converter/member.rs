@@ -1,7 +1,7 @@
 use std::collections::HashMap;
 
 use crate::{
-    model::member::{NotesHistory, ProjectMember, TaskHistory},
+    model::member::{CkeyLink, NotesHistory, ProjectMember, TaskHistory},
     shop::ShopData,
 };
 use serde::Deserialize;
@@ -152,11 +152,10 @@ impl Into<ProjectMember> for OldProjectMember {
             warns.push(NotesHistory::Current((user, timestamp, text)));
         }
 
-        if let Some(ckey) = self.ckey {
-            if ckey != String::new() {
-                notes.insert(0, NotesHistory::OldFormat(format!("**ckey:** {}", ckey)));
-            }
-        }
+        let ckey = self
+            .ckey
+            .filter(|ckey| ckey != &String::new())
+            .map(|ckey| CkeyLink::new(&ckey, None, true));
 
         ProjectMember {
             id: self.id.unwrap(),
@@ -166,9 +165,19 @@ impl Into<ProjectMember> for OldProjectMember {
             own_folder: HashMap::new(),
             score: self.score.unwrap_or(0),
             all_time_score: self.all_time_score.unwrap_or(0),
+            project_scores: HashMap::new(),
+            scores_migrated: false,
+            score_history: Vec::new(),
             last_activity,
             warns,
             notes,
+            ckey,
+            inactivity_warnings: HashMap::new(),
+            warn_appeals: Vec::new(),
+            achievements: HashMap::new(),
+            activity_streak: None,
+            score_transfers: Vec::new(),
+            application: None,
             shop_data: ShopData {
                 current_page: 0,
                 pages: Vec::new(),
@@ -180,6 +189,14 @@ impl Into<ProjectMember> for OldProjectMember {
             changed_sub_post: None,
             changed_role: None,
             changed_db: None,
+            changed_warn: None,
+            bulk_operation: None,
+            leaderboard_view: None,
+            season_end: None,
+            member_merge: None,
+            onboarding_project: None,
+            changed_application: None,
+            profile_view: None,
         }
     }
 }
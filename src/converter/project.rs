@@ -28,13 +28,24 @@ pub struct OldProject {
 impl Into<Project> for OldProject {
     fn into(self) -> Project {
         Project {
+            id: 0,
             name: self.name.unwrap(),
             max_tasks_per_user: self.max_tasks_per_user,
             tasks_forum: self.tasks_forum,
             waiter_role: self.waiter_role,
-            stat_posts: self.stat_posts,
+            forums: Vec::new(),
+            stat_posts: self
+                .stat_posts
+                .into_iter()
+                .map(|(role, msg)| (role, Vec::from([msg])))
+                .collect(),
             stat_channel: self.stat_channel,
             associated_roles: self.associated_roles,
+            required_sections: Vec::new(),
+            inactivity: None,
+            archive: None,
+            managers: Vec::new(),
+            digest: None,
         }
     }
 }
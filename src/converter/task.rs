@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use crate::model::task::{Task, TaskOption};
+use crate::model::task::{LastSave, Task, TaskOption};
 use serde::Deserialize;
 use serenity::{
     self,
@@ -42,8 +42,9 @@ impl Into<Task> for OldTask {
             members: TaskOption::new(self.members),
             start_date: Some(Timestamp::parse(&format!("{}T00:00:00Z", self.start_date)).unwrap()),
             end_date: TaskOption::new(None),
-            last_save: TaskOption::new(self.last_save),
+            last_save: TaskOption::new(self.last_save.map(LastSave::OldFormat)),
             ending_results: HashMap::new(),
+            close_results: HashMap::new(),
         }
     }
 }
@@ -1,6 +1,9 @@
-use crate::{commands::*, config::CONFIG, prelude::*, shop};
+use crate::{commands::*, config::CONFIG, model::tag::TageTypes, prelude::*, shop};
 use serenity::{
-    all::{async_trait, ForumEmoji, Reaction, ReactionType},
+    all::{
+        async_trait, EditThread, ForumEmoji, ForumTagId, GuildMemberUpdateEvent, Message,
+        MessageId, MessageUpdateEvent, Reaction, ReactionType,
+    },
     client::{Context, EventHandler},
     http::Http,
     model::{application::Interaction, gateway::Ready, id::GuildId},
@@ -35,6 +38,7 @@ impl EventHandler for Handler {
         tag_commands(&ctx, guild_id).await;
         config_commands(&ctx, guild_id).await;
         role_commands(&ctx, guild_id).await;
+        season_commands(&ctx, guild_id).await;
 
         COMMANDMANAGER.read().await.debug_print().await;
 
@@ -42,10 +46,22 @@ impl EventHandler for Handler {
 
         shop::shop_component_listeners().await;
         member::member_changer_listener().await;
+        member::warn_listener().await;
+        member::member_merge_listener().await;
+        member::ckey_listener().await;
+        member::transfer_listener().await;
+        member::onboarding_listener().await;
+        member::profile_listener().await;
         task::task_changer_listener().await;
+        task::tasks_bulk_listener().await;
+        leaderboard::leaderboard_listener().await;
+        season::season_listener().await;
         project::project_listen().await;
+        project::managers_listener().await;
         tag::tag_changer_listener().await;
 
+        leaderboard::LeaderboardManager::start_update(ctx.clone()).await;
+        project::start_inactivity_check(ctx.clone()).await;
         project::ProjectManager::start_update_stat(ctx).await;
 
         Logger::low("handler.ready", "bot is ready").await;
@@ -71,11 +87,41 @@ impl EventHandler for Handler {
                     .await;
             }
             Interaction::Component(ref component) => {
+                let caller = project::Caller::new(component.user.id, component.member.as_ref());
+                let values = project::component_values(component);
+
+                if !project::check_component(&component.data.custom_id, &caller, &values).await {
+                    Logger::if_ok(
+                        "handler.interaction_create",
+                        "cannot respond about denied access",
+                        component
+                            .create_response(&ctx.http, access_denied_response())
+                            .await,
+                    )
+                    .await;
+                    return;
+                }
+
                 command_man
                     .call_component(&component.data.custom_id, component, Arc::new(ctx))
                     .await;
             }
             Interaction::Modal(ref modal) => {
+                let caller = project::Caller::new(modal.user.id, modal.member.as_ref());
+                let values = project::modal_values(modal);
+
+                if !project::check_component(&modal.data.custom_id, &caller, &values).await {
+                    Logger::if_ok(
+                        "handler.interaction_create",
+                        "cannot respond about denied access",
+                        modal
+                            .create_response(&ctx.http, access_denied_response())
+                            .await,
+                    )
+                    .await;
+                    return;
+                }
+
                 command_man
                     .call_modal(&modal.data.custom_id, modal, Arc::new(ctx))
                     .await;
@@ -85,40 +131,23 @@ impl EventHandler for Handler {
     }
 
     async fn thread_create(&self, ctx: Context, thread: GuildChannel) {
-        let proj_man = project::PROJECTMANAGER.read().await;
-        let mut task_man = task::TASKMANAGER.write().await;
-        let mut thread = thread;
+        register_thread(&ctx, thread, false).await;
+    }
 
-        if let Some(_) = task_man.get_thread(thread.id) {
+    #[allow(unused_variables)]
+    async fn message_update(
+        &self,
+        ctx: Context,
+        old_if_available: Option<Message>,
+        new: Option<Message>,
+        event: MessageUpdateEvent,
+    ) {
+        if event.id.get() != event.channel_id.get() {
             return;
         }
 
-        if let Some(ref parent) = thread.parent_id {
-            if let Some(project) = proj_man.get_from_forum(parent) {
-                match task_man
-                    .new_task(
-                        &ctx,
-                        &mut thread,
-                        project.name().clone(),
-                        project.waiter_role.clone(),
-                    )
-                    .await
-                {
-                    Ok(_) => (),
-                    Err(e) => {
-                        Logger::error(
-                            "handler.thread_create",
-                            &format!(
-                            "error while creating task from thread \"{}\" for project \"{}\": {}",
-                            thread.name,
-                            project.name(),
-                            e
-                        ),
-                        )
-                        .await
-                    }
-                }
-            }
+        if let Ok(thread) = fetch_thread(&ctx, event.channel_id) {
+            register_thread(&ctx, thread, true).await;
         }
     }
 
@@ -153,6 +182,29 @@ impl EventHandler for Handler {
                 .await;
             }
         }
+
+        member::send_welcome(&ctx, &new_member).await;
+        project::ProjectManager::request_stat_update();
+    }
+
+    #[allow(unused_variables)]
+    async fn guild_member_update(
+        &self,
+        ctx: Context,
+        old_if_available: Option<Member>,
+        new: Option<Member>,
+        event: GuildMemberUpdateEvent,
+    ) {
+        let changed = match (old_if_available, new) {
+            (Some(old), Some(new)) => {
+                old.roles != new.roles || old.display_name() != new.display_name()
+            }
+            _ => true,
+        };
+
+        if changed {
+            project::ProjectManager::request_stat_update();
+        }
     }
 
     #[allow(unused_variables)]
@@ -175,6 +227,8 @@ impl EventHandler for Handler {
                 }
             }
         }
+
+        project::ProjectManager::request_stat_update();
     }
 
     async fn reaction_add(&self, ctx: Context, add_reaction: Reaction) {
@@ -225,6 +279,154 @@ impl EventHandler for Handler {
     }
 }
 
+async fn register_thread(ctx: &Context, thread: GuildChannel, recheck: bool) {
+    let proj_man = project::PROJECTMANAGER.read().await;
+    let mut task_man = task::TASKMANAGER.write().await;
+    let mut thread = thread;
+
+    if task_man.get_thread(thread.id).is_some() {
+        return;
+    }
+
+    let Some(parent) = thread.parent_id else {
+        return;
+    };
+
+    let Some(project) = proj_man.get_from_forum(&parent) else {
+        return;
+    };
+
+    if project.is_archived() {
+        return;
+    }
+
+    let needs_info_tags = tag::TAGSMANAGER
+        .read()
+        .await
+        .get_by_type(&parent, TageTypes::NeedsInfo)
+        .unwrap_or_default();
+
+    let withheld = thread
+        .applied_tags
+        .iter()
+        .any(|x| needs_info_tags.contains(x));
+
+    if recheck && !withheld {
+        return;
+    }
+
+    if !project.required_sections.is_empty() {
+        match thread
+            .id
+            .message(&ctx.http, MessageId::new(thread.id.get()))
+            .await
+        {
+            Ok(starter) => {
+                let missing = project.missing_sections(&starter.content);
+
+                if !missing.is_empty() {
+                    if !recheck {
+                        Logger::if_ok(
+                            "handler.register_thread",
+                            "cannot send message about missing sections",
+                            thread
+                                .send_message(
+                                    &ctx.http,
+                                    CreateMessage::new().content(loc!(
+                                        "task-template-missing-sections",
+                                        "sections" = missing
+                                            .iter()
+                                            .map(|x| format!("- {}", x))
+                                            .collect::<Vec<String>>()
+                                            .join("\n")
+                                    )),
+                                )
+                                .await,
+                        )
+                        .await;
+                    }
+
+                    if !needs_info_tags.is_empty() {
+                        if !withheld {
+                            let mut tags = thread.applied_tags.clone();
+                            tags.extend(needs_info_tags.iter().cloned());
+
+                            Logger::if_ok(
+                                "handler.register_thread",
+                                "cannot apply needs info tags",
+                                thread
+                                    .edit_thread(&ctx.http, EditThread::new().applied_tags(tags))
+                                    .await,
+                            )
+                            .await;
+                        }
+
+                        return;
+                    }
+                } else if withheld {
+                    let tags = thread
+                        .applied_tags
+                        .iter()
+                        .filter(|x| !needs_info_tags.contains(x))
+                        .cloned()
+                        .collect::<Vec<ForumTagId>>();
+
+                    Logger::if_ok(
+                        "handler.register_thread",
+                        "cannot remove needs info tags",
+                        thread
+                            .edit_thread(&ctx.http, EditThread::new().applied_tags(tags))
+                            .await,
+                    )
+                    .await;
+                }
+            }
+            Err(e) => {
+                Logger::debug(
+                    "handler.register_thread",
+                    &format!(
+                        "cannot fetch starter message of thread \"{}\": {}",
+                        thread.name, e
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+
+    match task_man
+        .new_task(
+            ctx,
+            &mut thread,
+            project.name().clone(),
+            &project.forum(parent),
+        )
+        .await
+    {
+        Ok(_) => (),
+        Err(e) => {
+            Logger::error(
+                "handler.thread_create",
+                &format!(
+                    "error while creating task from thread \"{}\" for project \"{}\": {}",
+                    thread.name,
+                    project.name(),
+                    e
+                ),
+            )
+            .await
+        }
+    }
+}
+
+fn access_denied_response() -> CreateInteractionResponse {
+    CreateInteractionResponse::Message(
+        CreateInteractionResponseMessage::new()
+            .content(loc!("project-access-denied"))
+            .ephemeral(true),
+    )
+}
+
 async fn sync_guild_commands(http: &Http, guild_id: &GuildId) {
     match http.get_guild_commands(guild_id.clone()).await {
         Ok(commands) => {
@@ -9,10 +9,17 @@ async fn main() {
     CONFIG.read().await;
     localization::LOCALIZATION.try_read().unwrap();
     shop::SHOPMANAGER.write().await.init().await;
+    achievement::ACHIEVEMENTMANAGER.write().await.init().await;
     member::MEMBERSMANAGER.write().await.init().await;
     task::TASKMANAGER.write().await.init().await;
+    member::MEMBERSMANAGER
+        .write()
+        .await
+        .backfill_score_history(&*task::TASKMANAGER.read().await)
+        .await;
     tag::TAGSMANAGER.write().await.init().await;
     project::PROJECTMANAGER.write().await.init().await;
+    season::SEASONMANAGER.write().await.init().await;
     role::ROLEMANAGER.write().await;
     load_env();
 
@@ -0,0 +1,366 @@
+use crate::{
+    model::member::{ProjectMember, TaskHistory},
+    prelude::*,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::Colour,
+    builder::CreateEmbed,
+    model::{
+        id::{RoleId, UserId},
+        timestamp::Timestamp,
+    },
+};
+use std::fs;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+pub static ACHIEVEMENTMANAGER: Lazy<Arc<RwLock<AchievementManager>>> = Lazy::new(|| {
+    Arc::new(RwLock::new(AchievementManager::new(
+        "achievements".to_string(),
+    )))
+});
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum AchievementCondition {
+    DoneTasks {
+        count: usize,
+        #[serde(default)]
+        project: Option<String>,
+    },
+    MentorTasks {
+        count: usize,
+        #[serde(default)]
+        project: Option<String>,
+    },
+    Score {
+        score: i64,
+    },
+    AllTimeScore {
+        score: i64,
+    },
+    ActivityStreak {
+        days: i64,
+    },
+}
+
+impl AchievementCondition {
+    fn count_tasks(tasks: &HashMap<String, Vec<TaskHistory>>, project: &Option<String>) -> usize {
+        tasks
+            .iter()
+            .filter(|(name, _)| project.as_ref().is_none_or(|project| project == *name))
+            .map(|(_, history)| {
+                history
+                    .iter()
+                    .map(|task| match task {
+                        TaskHistory::Current(map) => map.len(),
+                        TaskHistory::OldFormat(_) => 1,
+                    })
+                    .sum::<usize>()
+            })
+            .sum()
+    }
+
+    fn is_met(&self, member: &ProjectMember) -> bool {
+        match self {
+            Self::DoneTasks { count, project } => {
+                Self::count_tasks(&member.done_tasks, project) >= *count
+            }
+            Self::MentorTasks { count, project } => {
+                Self::count_tasks(&member.mentor_tasks, project) >= *count
+            }
+            Self::Score { score } => member.total_score() >= *score,
+            Self::AllTimeScore { score } => member.all_time_score >= *score,
+            Self::ActivityStreak { days } => member
+                .activity_streak
+                .as_ref()
+                .is_some_and(|streak| streak.days() >= *days),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    #[serde(default)]
+    pub emoji: Option<String>,
+    pub condition: AchievementCondition,
+    #[serde(default)]
+    pub role: Option<RoleId>,
+    #[serde(default)]
+    pub score: Option<i64>,
+}
+
+impl Achievement {
+    pub fn title(&self) -> String {
+        match &self.emoji {
+            Some(emoji) => format!("{} {}", emoji, loc!(&self.name)),
+            None => loc!(&self.name),
+        }
+    }
+
+    fn to_embed(&self, member: UserId) -> CreateEmbed {
+        let mut embed = CreateEmbed::new()
+            .title(loc!(
+                "achievement-unlocked-title",
+                "achievement" = self.title()
+            ))
+            .color(Colour::GOLD)
+            .description(loc!(
+                "achievement-unlocked-description",
+                "member" = member.get(),
+                "description" = loc!(&self.description)
+            ));
+
+        if let Some(score) = self.score {
+            embed = embed.field(
+                loc!("achievement-reward-name"),
+                loc!("achievement-reward-score", "score" = score),
+                true,
+            );
+        }
+
+        if let Some(role) = self.role {
+            embed = embed.field(
+                loc!("achievement-reward-name"),
+                format!("<@&{}>", role.get()),
+                true,
+            );
+        }
+
+        embed
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ActivityStreak {
+    pub start: Timestamp,
+    pub last: Timestamp,
+}
+
+impl ActivityStreak {
+    fn day(time: &Timestamp) -> i64 {
+        time.unix_timestamp().div_euclid(86400)
+    }
+
+    pub fn days(&self) -> i64 {
+        Self::day(&self.last) - Self::day(&self.start) + 1
+    }
+
+    pub fn touch(streak: &mut Option<Self>, now: Timestamp) {
+        match streak {
+            Some(streak) if Self::day(&now) - Self::day(&streak.last) <= 1 => streak.last = now,
+            _ => {
+                *streak = Some(Self {
+                    start: now,
+                    last: now,
+                })
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct AchievementManager {
+    achievements: Vec<Achievement>,
+    achievements_path: String,
+}
+
+impl AchievementManager {
+    fn new(achievements_path: String) -> Self {
+        Self {
+            achievements: Vec::new(),
+            achievements_path,
+        }
+    }
+
+    pub async fn init(&mut self) {
+        let mut achievements = Vec::new();
+
+        if !fs::exists(DATA_PATH.join(&self.achievements_path)).unwrap() {
+            fs::create_dir_all(DATA_PATH.join(&self.achievements_path))
+                .expect("error while creating folder data/achievements");
+        }
+
+        for entry in WalkDir::new(DATA_PATH.join(&self.achievements_path)) {
+            let entry = match entry {
+                Ok(s) => s,
+                Err(error) => {
+                    Logger::error(
+                        "achievement_man.init",
+                        &format!("error while reading achievements: {}", error),
+                    )
+                    .await;
+                    continue;
+                }
+            };
+
+            if !entry.path().is_file() {
+                continue;
+            }
+
+            let content: Vec<Achievement> =
+                match serde_yaml::from_str(read_file(&entry.path().to_path_buf()).as_str()) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        Logger::error(
+                            "achievement_man.init",
+                            &format!(
+                                "error while parsing achievements file \"{}\": {}",
+                                entry.file_name().to_str().unwrap(),
+                                e
+                            ),
+                        )
+                        .await;
+                        continue;
+                    }
+                };
+
+            achievements.extend(content);
+        }
+
+        self.achievements = achievements;
+
+        Logger::debug(
+            "achievement_man.init",
+            &format!(
+                "initialized from achievements/* with {} achievements",
+                self.achievements.len()
+            ),
+        )
+        .await;
+    }
+
+    pub fn project_references(&self, project: &str) -> Vec<String> {
+        self.achievements
+            .iter()
+            .filter(|x| match &x.condition {
+                AchievementCondition::DoneTasks { project: p, .. }
+                | AchievementCondition::MentorTasks { project: p, .. } => {
+                    p.as_deref() == Some(project)
+                }
+                _ => false,
+            })
+            .map(|x| x.title())
+            .collect()
+    }
+
+    pub fn get(&self, id: &String) -> Option<&Achievement> {
+        self.achievements.iter().find(|x| &x.id == id)
+    }
+}
+
+impl ProjectMember {
+    pub fn unlock_achievements(
+        &mut self,
+        achievement_man: &AchievementManager,
+    ) -> Vec<Achievement> {
+        let mut unlocked = Vec::new();
+
+        // score rewards may unlock further score achievements, so repeat until nothing changes
+        loop {
+            let new: Vec<&Achievement> = achievement_man
+                .achievements
+                .iter()
+                .filter(|x| !self.achievements.contains_key(&x.id) && x.condition.is_met(self))
+                .collect();
+
+            if new.is_empty() {
+                break;
+            }
+
+            for achievement in new {
+                self.achievements
+                    .insert(achievement.id.clone(), Timestamp::now());
+
+                if let Some(score) = achievement.score {
+                    self.credit(score);
+                }
+
+                unlocked.push(achievement.clone());
+            }
+        }
+
+        unlocked
+    }
+
+    pub async fn check_achievements(&mut self) {
+        let unlocked = self.unlock_achievements(&*ACHIEVEMENTMANAGER.read().await);
+
+        if unlocked.is_empty() {
+            return;
+        }
+
+        self.update().await;
+
+        let id = self.id;
+        tokio::spawn(async move {
+            announce_achievements(id, unlocked).await;
+        });
+    }
+}
+
+async fn announce_achievements(id: UserId, unlocked: Vec<Achievement>) {
+    let channel = CONFIG.read().await.achievement_channel;
+
+    for achievement in unlocked {
+        Logger::medium(
+            "member.check_achievements",
+            &format!(
+                "member {} unlocked achievement \"{}\"",
+                id.get(),
+                achievement.id
+            ),
+        )
+        .await;
+
+        if let Some(score) = achievement.score {
+            Logger::low(
+                "member.check_achievements",
+                &format!(
+                    "score of member {} changed by {} for achievement \"{}\"",
+                    id.get(),
+                    score,
+                    achievement.id
+                ),
+            )
+            .await;
+        }
+
+        if let Some(role) = achievement.role {
+            if let Ok(member) = fetch_member(&id).await {
+                Logger::if_ok(
+                    "member.check_achievements",
+                    "cannot add achievement role",
+                    member.add_role(get_http(), role).await,
+                )
+                .await;
+            }
+        }
+
+        let message = CreateMessage::new().embed(achievement.to_embed(id));
+        let result = match channel {
+            Some(channel) => channel.send_message(get_http(), message).await,
+            None => id.direct_message(get_http(), message).await,
+        };
+
+        if let Err(e) = result {
+            Logger::debug(
+                "member.check_achievements",
+                &format!(
+                    "cannot announce achievement \"{}\" of member {}: {}",
+                    achievement.id,
+                    id.get(),
+                    e
+                ),
+            )
+            .await;
+        }
+    }
+}
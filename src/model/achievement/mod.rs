@@ -0,0 +1,5 @@
+mod achievement;
+
+pub use achievement::{
+    Achievement, AchievementCondition, AchievementManager, ActivityStreak, ACHIEVEMENTMANAGER,
+};
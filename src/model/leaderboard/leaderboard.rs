@@ -0,0 +1,440 @@
+use crate::{
+    model::{
+        member::{MembersManager, ProjectMember, TaskHistory, MEMBERSMANAGER},
+        task::{TaskManager, TASKMANAGER},
+    },
+    prelude::*,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use serenity::{
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed, EditMessage},
+    model::{
+        id::{ChannelId, MessageId, UserId},
+        timestamp::Timestamp,
+    },
+};
+use std::{collections::HashMap, sync::Arc};
+use tokio::{
+    sync::RwLock,
+    time::{sleep, Duration},
+};
+
+pub static LEADERBOARDMANAGER: Lazy<Arc<RwLock<LeaderboardManager>>> =
+    Lazy::new(|| Arc::new(RwLock::new(LeaderboardManager::new())));
+
+const PAGE_SIZE: usize = 15;
+const DEFAULT_WINDOW_DAYS: i64 = 30;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum LeaderboardScope {
+    Score,
+    AllTimeScore,
+    DoneTasks,
+    MentorTasks,
+    RecentTasks,
+}
+
+impl LeaderboardScope {
+    pub fn from_choice(choice: &str) -> Self {
+        match choice {
+            "leaderboard-scope-all-time-score" => Self::AllTimeScore,
+            "leaderboard-scope-done-tasks" => Self::DoneTasks,
+            "leaderboard-scope-mentor-tasks" => Self::MentorTasks,
+            "leaderboard-scope-recent-tasks" => Self::RecentTasks,
+            _ => Self::Score,
+        }
+    }
+
+    fn locale_key(&self) -> &'static str {
+        match self {
+            Self::Score => "leaderboard-scope-score",
+            Self::AllTimeScore => "leaderboard-scope-all-time-score",
+            Self::DoneTasks => "leaderboard-scope-done-tasks",
+            Self::MentorTasks => "leaderboard-scope-mentor-tasks",
+            Self::RecentTasks => "leaderboard-scope-recent-tasks",
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Leaderboard {
+    pub scope: LeaderboardScope,
+    pub project: Option<String>,
+    pub days: Option<i64>,
+}
+
+impl Leaderboard {
+    pub fn new(scope: LeaderboardScope, project: Option<String>, days: Option<i64>) -> Self {
+        Self {
+            scope,
+            project,
+            days,
+        }
+    }
+
+    pub async fn rows(&self) -> Vec<(UserId, i64)> {
+        let mem_man = MEMBERSMANAGER.read().await;
+        let task_man = TASKMANAGER.read().await;
+
+        self.rows_from(&mem_man, &task_man)
+    }
+
+    fn rows_from(&self, mem_man: &MembersManager, task_man: &TaskManager) -> Vec<(UserId, i64)> {
+        let now = Timestamp::now().unix_timestamp();
+
+        let mut rows: Vec<(UserId, i64)> = mem_man
+            .get_all()
+            .into_iter()
+            .map(|member| (member.id, self.value(member, task_man, now)))
+            .filter(|(_, value)| *value != 0)
+            .collect();
+
+        rows.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        rows
+    }
+
+    fn value(&self, member: &ProjectMember, task_man: &TaskManager, now: i64) -> i64 {
+        match self.scope {
+            LeaderboardScope::Score => match &self.project {
+                Some(project) => member.project_score(project),
+                None => member.total_score(),
+            },
+            LeaderboardScope::AllTimeScore => member.all_time_score,
+            LeaderboardScope::DoneTasks => self.count_tasks(&member.done_tasks),
+            LeaderboardScope::MentorTasks => self.count_tasks(&member.mentor_tasks),
+            LeaderboardScope::RecentTasks => {
+                let since = now - self.days.unwrap_or(DEFAULT_WINDOW_DAYS) * 86400;
+
+                self.count_closed_since(&member.done_tasks, task_man, since)
+                    + self.count_closed_since(&member.mentor_tasks, task_man, since)
+            }
+        }
+    }
+
+    fn histories<'a>(
+        &self,
+        tasks: &'a HashMap<String, Vec<TaskHistory>>,
+    ) -> Vec<&'a Vec<TaskHistory>> {
+        match &self.project {
+            Some(project) => tasks.get(project).into_iter().collect(),
+            None => tasks.values().collect(),
+        }
+    }
+
+    fn count_tasks(&self, tasks: &HashMap<String, Vec<TaskHistory>>) -> i64 {
+        let mut count = 0;
+
+        for history in self.histories(tasks) {
+            for task in history.iter() {
+                count += match task {
+                    TaskHistory::Current(map) => map.len() as i64,
+                    TaskHistory::OldFormat(_) => 1,
+                };
+            }
+        }
+
+        count
+    }
+
+    fn count_closed_since(
+        &self,
+        tasks: &HashMap<String, Vec<TaskHistory>>,
+        task_man: &TaskManager,
+        since: i64,
+    ) -> i64 {
+        let mut count = 0;
+
+        for history in self.histories(tasks) {
+            for task in history.iter() {
+                if let TaskHistory::Current(map) = task {
+                    for (time, id) in map.iter() {
+                        let closed = task_man
+                            .get(*id)
+                            .and_then(|task| *task.end_date.get())
+                            .unwrap_or(*time);
+
+                        if closed.unix_timestamp() >= since {
+                            count += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        count
+    }
+
+    pub fn pages(rows: &[(UserId, i64)]) -> usize {
+        rows.len().div_ceil(PAGE_SIZE).max(1)
+    }
+
+    fn title(&self) -> String {
+        let mut title = loc!(self.scope.locale_key());
+
+        if let Some(project) = &self.project {
+            title = format!("{} — {}", title, project);
+        }
+
+        if self.scope == LeaderboardScope::RecentTasks {
+            title = loc!(
+                "leaderboard-window-title",
+                "title" = title,
+                "days" = self.days.unwrap_or(DEFAULT_WINDOW_DAYS)
+            );
+        }
+
+        title
+    }
+
+    pub fn to_embed(
+        &self,
+        rows: &[(UserId, i64)],
+        page: usize,
+        caller: Option<UserId>,
+    ) -> CreateEmbed {
+        let pages = Self::pages(rows);
+        let page = page.min(pages - 1);
+
+        let mut lines = Vec::new();
+        for (index, (id, value)) in rows
+            .iter()
+            .enumerate()
+            .skip(page * PAGE_SIZE)
+            .take(PAGE_SIZE)
+        {
+            lines.push(format!("`#{}` <@{}> — **{}**", index + 1, id.get(), value));
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title(loc!("leaderboard-embed-title", "title" = self.title()))
+            .color(Colour::GOLD)
+            .description(match lines.is_empty() {
+                true => loc!("leaderboard-embed-empty"),
+                false => lines.join("\n"),
+            })
+            .footer(serenity::all::CreateEmbedFooter::new(loc!(
+                "leaderboard-embed-page",
+                "page" = page + 1,
+                "pages" = pages
+            )))
+            .timestamp(Timestamp::now());
+
+        if let Some(caller) = caller {
+            embed = embed.field(
+                loc!("leaderboard-embed-your-rank-name"),
+                match rows.iter().position(|(id, _)| id == &caller) {
+                    Some(index) => loc!(
+                        "leaderboard-embed-your-rank",
+                        "rank" = index + 1,
+                        "total" = rows.len(),
+                        "value" = rows[index].1
+                    ),
+                    None => loc!("leaderboard-embed-not-ranked"),
+                },
+                false,
+            );
+        }
+
+        embed
+    }
+
+    pub fn page_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("leaderboard:previous")
+                .emoji('◀')
+                .style(ButtonStyle::Secondary),
+            CreateButton::new("leaderboard:next")
+                .emoji('▶')
+                .style(ButtonStyle::Secondary),
+        ]))])
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct LeaderboardPost {
+    pub channel: ChannelId,
+    pub message: MessageId,
+    pub board: Leaderboard,
+}
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct LeaderboardManager {
+    posts: Vec<LeaderboardPost>,
+}
+
+impl LeaderboardManager {
+    fn new() -> Self {
+        let content = read_file(&DATA_PATH.join("leaderboards.json"));
+
+        match content.as_str() {
+            "" => Self::default(),
+            _ => match serde_json::from_str(&content) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprint!("leaderboard manager deserialize error: {}", e);
+                    Self::default()
+                }
+            },
+        }
+    }
+
+    async fn write_data(&self) {
+        write_file(
+            &DATA_PATH.join("leaderboards.json"),
+            match serde_json::to_string(&self) {
+                Ok(c) => c,
+                Err(e) => {
+                    Logger::error("leaderboard_man.serialize", e.to_string().as_str()).await;
+                    return;
+                }
+            },
+        );
+    }
+
+    pub async fn add_post(&mut self, post: LeaderboardPost) {
+        Logger::medium(
+            "leaderboard_man.add_post",
+            &format!(
+                "leaderboard {:?} posted in channel {} as message {}",
+                post.board,
+                post.channel.get(),
+                post.message.get()
+            ),
+        )
+        .await;
+
+        self.posts.push(post);
+        self.write_data().await;
+    }
+
+    pub async fn rename_project(&mut self, from: &str, into: &str) {
+        let mut found = false;
+
+        for post in self.posts.iter_mut() {
+            if post.board.project.as_deref() == Some(from) {
+                post.board.project = Some(into.to_string());
+                found = true;
+            }
+        }
+
+        if found {
+            self.write_data().await;
+        }
+    }
+
+    pub async fn start_update(ctx: Context) {
+        tokio::spawn(async move {
+            let timer = CONFIG.read().await.project_stat_update_duration;
+
+            loop {
+                let mut man = LEADERBOARDMANAGER.write().await;
+                man.update_posts(&ctx).await;
+                drop(man);
+
+                sleep(Duration::from_secs(timer)).await;
+            }
+        });
+    }
+
+    async fn update_posts(&mut self, ctx: &Context) {
+        let mut removed = Vec::new();
+
+        for post in self.posts.iter() {
+            let rows = post.board.rows().await;
+            let embed = post.board.to_embed(&rows, 0, None);
+
+            match post.channel.message(&ctx.http, post.message).await {
+                Ok(mut message) => {
+                    Logger::if_ok(
+                        "leaderboard_man.update_posts",
+                        "cannot edit leaderboard post",
+                        message
+                            .edit(&ctx.http, EditMessage::new().embed(embed))
+                            .await,
+                    )
+                    .await;
+                }
+                Err(_) => removed.push(post.message),
+            }
+        }
+
+        if !removed.is_empty() {
+            self.posts.retain(|post| !removed.contains(&post.message));
+            self.write_data().await;
+
+            Logger::medium(
+                "leaderboard_man.update_posts",
+                &format!("removed deleted leaderboard posts {:?}", removed),
+            )
+            .await;
+        }
+
+        Logger::debug("leaderboard_man.update_posts", "updated leaderboard posts").await;
+    }
+}
+
+pub async fn leaderboard_listener() {
+    #[listen_component("leaderboard:previous")]
+    async fn previous(ctx: &Context, inter: ComponentInteraction) {
+        turn_page(ctx, inter, -1).await;
+    }
+
+    #[listen_component("leaderboard:next")]
+    async fn next(ctx: &Context, inter: ComponentInteraction) {
+        turn_page(ctx, inter, 1).await;
+    }
+}
+
+async fn turn_page(ctx: &Context, inter: ComponentInteraction, step: i64) {
+    let view = member::MEMBERSMANAGER
+        .write()
+        .await
+        .get(inter.user.id)
+        .await
+        .unwrap()
+        .leaderboard_view
+        .clone();
+
+    let Some((board, page)) = view else {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("leaderboard-no-view"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    };
+
+    let rows = board.rows().await;
+    let pages = Leaderboard::pages(&rows) as i64;
+    let page = (page as i64 + step).rem_euclid(pages) as usize;
+
+    let embed = board.to_embed(&rows, page, Some(inter.user.id));
+
+    member::MEMBERSMANAGER
+        .write()
+        .await
+        .get_mut(inter.user.id)
+        .await
+        .unwrap()
+        .leaderboard_view = Some((board, page));
+
+    inter
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(Leaderboard::page_buttons()),
+            ),
+        )
+        .await
+        .unwrap();
+}
@@ -0,0 +1,6 @@
+mod leaderboard;
+
+pub use leaderboard::{
+    leaderboard_listener, Leaderboard, LeaderboardManager, LeaderboardPost, LeaderboardScope,
+    LEADERBOARDMANAGER,
+};
@@ -44,7 +44,7 @@ pub async fn member_changer_listener() {
                             loc!("member-changer-modal-score-components-score-title"),
                             "member-changer:score:score",
                         )
-                        .value(member.score.to_string()),
+                        .value(member.total_score().to_string()),
                     )])),
                 ),
             )
@@ -261,7 +261,7 @@ pub async fn member_changer_listener() {
                                     }
                                 };
 
-                            member.change_score(score - member.score).await;
+                            member.change_score(score - member.total_score()).await;
                         }
                     }
                     _ => (),
@@ -1045,10 +1045,8 @@ pub async fn member_changer_listener() {
     async fn warn_add_submit(ctx: &Context, inter: ModalInteraction) {
         let mut mem_man = member::MEMBERSMANAGER.write().await;
         let author = mem_man.get(inter.user.id).await.unwrap().clone();
-        let member = mem_man
-            .get_mut(author.changed_member.unwrap())
-            .await
-            .unwrap();
+        let member_id = author.changed_member.unwrap();
+        let member = mem_man.get_mut(member_id).await.unwrap();
 
         for row in inter.data.components.iter() {
             for comp in row.components.iter() {
@@ -1076,6 +1074,9 @@ pub async fn member_changer_listener() {
             )
             .await
             .unwrap();
+        drop(mem_man);
+
+        member::escalate_warns(ctx, member_id).await;
     }
 
     #[listen_component("member-changer:notes:note-remove")]
@@ -0,0 +1,282 @@
+use crate::{
+    connect::get_user_id,
+    model::member::{MembersManager, NotesHistory, ProjectMember, MEMBERSMANAGER},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed},
+    model::{
+        id::{MessageId, UserId},
+        timestamp::Timestamp,
+    },
+};
+
+const LEGACY_NOTE_PREFIX: &str = "**ckey:** ";
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CkeyLink {
+    pub ckey: String,
+    pub verified: bool,
+    pub verified_by: Option<UserId>,
+    pub linked: Timestamp,
+    #[serde(default)]
+    pub message: Option<MessageId>,
+}
+
+impl CkeyLink {
+    pub fn new(ckey: &str, verified_by: Option<UserId>, verified: bool) -> Self {
+        Self {
+            ckey: normalize_ckey(ckey),
+            verified,
+            verified_by,
+            linked: Timestamp::now(),
+            message: None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match (self.verified, self.verified_by) {
+            (true, Some(user)) => loc!(
+                "ckey-link-verified-by",
+                "ckey" = self.ckey,
+                "user" = user.get()
+            ),
+            (true, None) => loc!("ckey-link-verified", "ckey" = self.ckey),
+            (false, _) => loc!("ckey-link-unverified", "ckey" = self.ckey),
+        }
+    }
+}
+
+pub fn normalize_ckey(key: &str) -> String {
+    key.chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .collect::<String>()
+        .to_lowercase()
+}
+
+impl ProjectMember {
+    pub fn migrate_ckey_note(&mut self) -> bool {
+        if self.ckey.is_some() {
+            return false;
+        }
+
+        let Some(index) = self.notes.iter().position(|note| match note {
+            NotesHistory::OldFormat(string) => string.starts_with(LEGACY_NOTE_PREFIX),
+            NotesHistory::Current(_) => false,
+        }) else {
+            return false;
+        };
+
+        if let NotesHistory::OldFormat(string) = self.notes.remove(index) {
+            self.ckey = Some(CkeyLink::new(
+                string.trim_start_matches(LEGACY_NOTE_PREFIX),
+                None,
+                true,
+            ));
+        }
+
+        true
+    }
+
+    pub async fn set_ckey(&mut self, link: Option<CkeyLink>) {
+        Logger::medium(
+            "member.set_ckey",
+            &format!("ckey of member {} set to {:?}", self.id.get(), link),
+        )
+        .await;
+
+        self.ckey = link;
+        self.update().await;
+    }
+}
+
+impl MembersManager {
+    pub fn get_by_ckey(&self, ckey: &str) -> Vec<&ProjectMember> {
+        let ckey = normalize_ckey(ckey);
+
+        self.get_all()
+            .into_iter()
+            .filter(|member| {
+                member
+                    .ckey
+                    .as_ref()
+                    .is_some_and(|x| normalize_ckey(&x.ckey) == ckey)
+            })
+            .collect()
+    }
+}
+
+pub async fn lookup_embed(members: Vec<&ProjectMember>, ckey: Option<String>) -> CreateEmbed {
+    let mut embed = CreateEmbed::new()
+        .title(loc!("ckey-lookup-title"))
+        .color(Colour::BLUE);
+
+    if let Some(ckey) = &ckey {
+        embed = embed.field(
+            loc!("ckey-lookup-user-id-name", "ckey" = ckey),
+            format!(
+                "`{}`",
+                get_user_id(ckey.clone())
+                    .await
+                    .unwrap_or(loc!("ckey-lookup-user-id-not-found"))
+            ),
+            false,
+        );
+    }
+
+    embed.description(match members.is_empty() {
+        true => loc!("ckey-lookup-not-found"),
+        false => box_list(
+            members
+                .iter()
+                .map(|member| {
+                    format!(
+                        "<@{}> — {}",
+                        member.id.get(),
+                        match &member.ckey {
+                            Some(link) => link.describe(),
+                            None => loc!("ckey-link-none"),
+                        }
+                    )
+                })
+                .collect(),
+        ),
+    })
+}
+
+pub fn verify_buttons() -> Vec<CreateActionRow> {
+    Vec::from([CreateActionRow::Buttons(Vec::from([
+        CreateButton::new("ckey-link:approve")
+            .label(loc!("ckey-link-approve-button"))
+            .style(ButtonStyle::Success),
+        CreateButton::new("ckey-link:deny")
+            .label(loc!("ckey-link-deny-button"))
+            .style(ButtonStyle::Danger),
+    ]))])
+}
+
+async fn resolve_link(ctx: &Context, inter: ComponentInteraction, approve: bool) {
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    let Some(member_id) = mem_man
+        .get_all()
+        .iter()
+        .find(|member| {
+            member
+                .ckey
+                .as_ref()
+                .is_some_and(|x| !x.verified && x.message == Some(inter.message.id))
+        })
+        .map(|member| member.id)
+    else {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("ckey-link-not-found"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    };
+
+    let mut link = mem_man.find(member_id).unwrap().ckey.clone().unwrap();
+
+    if approve
+        && mem_man
+            .get_by_ckey(&link.ckey)
+            .iter()
+            .any(|member| member.id != member_id && member.ckey.as_ref().unwrap().verified)
+    {
+        drop(mem_man);
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("link-ckey-command-taken", "ckey" = link.ckey))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    }
+
+    let member = mem_man.get_mut(member_id).await.unwrap();
+
+    if approve {
+        link.verified = true;
+        link.verified_by = Some(inter.user.id);
+        member.set_ckey(Some(link.clone())).await;
+    } else {
+        member.set_ckey(None).await;
+    }
+    drop(mem_man);
+
+    let result = match approve {
+        true => loc!(
+            "ckey-link-approved",
+            "ckey" = link.ckey,
+            "user" = inter.user.id.get()
+        ),
+        false => loc!(
+            "ckey-link-denied",
+            "ckey" = link.ckey,
+            "user" = inter.user.id.get()
+        ),
+    };
+
+    if let Err(e) = member_id
+        .direct_message(&ctx.http, CreateMessage::new().content(result.clone()))
+        .await
+    {
+        Logger::debug(
+            "ckey.resolve_link",
+            &format!(
+                "cannot send ckey link result to member {}: {}",
+                member_id.get(),
+                e
+            ),
+        )
+        .await;
+    }
+
+    inter
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!(
+                        "{}\n{}",
+                        loc!(
+                            "ckey-link-request",
+                            "member" = member_id.get(),
+                            "ckey" = link.ckey
+                        ),
+                        result
+                    ))
+                    .components(Vec::new()),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn ckey_listener() {
+    #[listen_component("ckey-link:approve")]
+    async fn approve(ctx: &Context, inter: ComponentInteraction) {
+        resolve_link(ctx, inter, true).await;
+    }
+
+    #[listen_component("ckey-link:deny")]
+    async fn deny(ctx: &Context, inter: ComponentInteraction) {
+        resolve_link(ctx, inter, false).await;
+    }
+}
@@ -0,0 +1,341 @@
+use crate::{
+    model::{
+        member::{
+            Application, CkeyLink, NotesHistory, ProjectMember, ScoreTransfer, TaskHistory,
+            WarnAppeal, MEMBERSMANAGER,
+        },
+        project::DIGESTSTATE,
+        season::SEASONMANAGER,
+        task::{TaskManager, TaskTranscript, TASKMANAGER},
+    },
+    prelude::*,
+};
+use serde::Serialize;
+use serde_json;
+use serenity::model::{
+    id::{ChannelId, UserId},
+    timestamp::Timestamp,
+};
+use std::collections::HashMap;
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub enum ExportTaskRole {
+    InWork,
+    Done,
+    Mentor,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct ExportTask {
+    pub id: u32,
+    pub project: String,
+    pub name: Option<String>,
+    pub thread_id: Option<ChannelId>,
+    pub link: Option<String>,
+    pub role: ExportTaskRole,
+    pub date: Option<Timestamp>,
+}
+
+#[derive(Serialize, Clone, Debug)]
+pub struct MemberExport {
+    pub id: UserId,
+    pub exported: Timestamp,
+    pub score: i64,
+    pub all_time_score: i64,
+    pub project_scores: HashMap<String, i64>,
+    pub ckey: Option<CkeyLink>,
+    pub own_folder: HashMap<String, Option<String>>,
+    pub last_activity: HashMap<String, Timestamp>,
+    pub tasks: Vec<ExportTask>,
+    pub old_format_tasks: Vec<String>,
+    pub notes: Vec<NotesHistory>,
+    pub warns: Vec<NotesHistory>,
+    pub warn_appeals: Vec<WarnAppeal>,
+    pub score_transfers: Vec<ScoreTransfer>,
+    pub application: Option<Application>,
+}
+
+impl MemberExport {
+    pub async fn new(member: &ProjectMember) -> Self {
+        let guild = CONFIG.read().await.guild;
+        let task_man = TASKMANAGER.read().await;
+
+        let mut export = Self {
+            id: member.id,
+            exported: Timestamp::now(),
+            score: member.total_score(),
+            all_time_score: member.all_time_score,
+            project_scores: member.project_scores.clone(),
+            ckey: member.ckey.clone(),
+            own_folder: member.own_folder.clone(),
+            last_activity: member.last_activity.clone(),
+            tasks: Vec::new(),
+            old_format_tasks: Vec::new(),
+            notes: member.notes.clone(),
+            warns: member.warns.clone(),
+            warn_appeals: member.warn_appeals.clone(),
+            score_transfers: member.score_transfers.clone(),
+            application: member.application.clone(),
+        };
+
+        for (project, ids) in member.in_tasks.iter() {
+            for id in ids.iter() {
+                export.push_task(&task_man, guild, project, *id, None, ExportTaskRole::InWork);
+            }
+        }
+
+        for (tasks, role) in [
+            (&member.done_tasks, ExportTaskRole::Done),
+            (&member.mentor_tasks, ExportTaskRole::Mentor),
+        ] {
+            for (project, history) in tasks.iter() {
+                for task in history.iter() {
+                    match task {
+                        TaskHistory::Current(map) => {
+                            for (time, id) in map.iter() {
+                                export.push_task(
+                                    &task_man,
+                                    guild,
+                                    project,
+                                    *id,
+                                    Some(*time),
+                                    role.clone(),
+                                );
+                            }
+                        }
+                        TaskHistory::OldFormat(string) => {
+                            export.old_format_tasks.push(string.clone())
+                        }
+                    }
+                }
+            }
+        }
+
+        export
+            .tasks
+            .sort_by_key(|task| (task.date.map(|x| x.unix_timestamp()), task.id));
+
+        export
+    }
+
+    fn push_task(
+        &mut self,
+        task_man: &TaskManager,
+        guild: u64,
+        project: &str,
+        id: u32,
+        date: Option<Timestamp>,
+        role: ExportTaskRole,
+    ) {
+        let task = task_man.get(id);
+
+        self.tasks.push(ExportTask {
+            id,
+            project: project.to_string(),
+            name: task.map(|task| task.name.get().clone()),
+            thread_id: task.map(|task| task.thread_id),
+            link: task.map(|task| {
+                format!(
+                    "https://discord.com/channels/{}/{}",
+                    guild,
+                    task.thread_id.get()
+                )
+            }),
+            role,
+            date,
+        });
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&self).unwrap_or_default()
+    }
+
+    pub fn to_markdown(&self) -> String {
+        let mut text = format!(
+            "# {}\n\n",
+            loc!("member-export-title", "member" = self.id.get())
+        );
+
+        text = format!(
+            "{}{}\n\n",
+            text,
+            loc!(
+                "member-export-summary",
+                "exported" = self.exported,
+                "score" = self.score,
+                "all_time_score" = self.all_time_score
+            )
+        );
+
+        if let Some(ckey) = &self.ckey {
+            text = format!("{}**ckey:** {}\n\n", text, ckey.describe());
+        }
+
+        text = format!("{}## {}\n\n", text, loc!("member-export-folders-name"));
+        for (db, folder) in self.own_folder.iter() {
+            text = format!(
+                "{}- {}: {}\n",
+                text,
+                db,
+                folder.clone().unwrap_or(loc!("member-export-none"))
+            );
+        }
+
+        text = format!("{}\n## {}\n\n", text, loc!("member-export-activity-name"));
+        for (project, time) in self.last_activity.iter() {
+            text = format!("{}- {}: {}\n", text, project, time);
+        }
+
+        for (role, name) in [
+            (ExportTaskRole::InWork, "member-export-in-tasks-name"),
+            (ExportTaskRole::Done, "member-export-done-tasks-name"),
+            (ExportTaskRole::Mentor, "member-export-mentor-tasks-name"),
+        ] {
+            text = format!("{}\n## {}\n\n", text, loc!(name));
+
+            for task in self.tasks.iter().filter(|task| task.role == role) {
+                text = format!(
+                    "{}- `{}` [{}] {}{}{}\n",
+                    text,
+                    task.id,
+                    task.project,
+                    task.name
+                        .clone()
+                        .unwrap_or(loc!("member-export-unknown-task")),
+                    match &task.link {
+                        Some(link) => format!(" — {}", link),
+                        None => String::new(),
+                    },
+                    match task.date {
+                        Some(date) => format!(" ({})", date),
+                        None => String::new(),
+                    }
+                );
+            }
+        }
+
+        if !self.old_format_tasks.is_empty() {
+            text = format!("{}\n## {}\n\n", text, loc!("member-export-old-tasks-name"));
+            for task in self.old_format_tasks.iter() {
+                text = format!("{}{}\n", text, task);
+            }
+        }
+
+        for (notes, name) in [
+            (&self.notes, "member-export-notes-name"),
+            (&self.warns, "member-export-warns-name"),
+        ] {
+            text = format!("{}\n## {}\n\n", text, loc!(name));
+
+            for note in notes.iter() {
+                text = format!(
+                    "{}- {}\n",
+                    text,
+                    match note {
+                        NotesHistory::OldFormat(string) => string.clone(),
+                        NotesHistory::Current((user, time, string)) => {
+                            format!("{} ({}): {}", time, user.get(), string)
+                        }
+                    }
+                );
+            }
+        }
+
+        if !self.warn_appeals.is_empty() {
+            text = format!("{}\n## {}\n\n", text, loc!("member-export-appeals-name"));
+            for appeal in self.warn_appeals.iter() {
+                text = format!(
+                    "{}- {} ({:?}): {}\n",
+                    text, appeal.created, appeal.state, appeal.text
+                );
+            }
+        }
+
+        if !self.project_scores.is_empty() {
+            text = format!(
+                "{}\n## {}\n\n",
+                text,
+                loc!("member-export-project-scores-name")
+            );
+            for (project, score) in self.project_scores.iter() {
+                text = format!("{}- {}: {}\n", text, project, score);
+            }
+        }
+
+        if !self.score_transfers.is_empty() {
+            text = format!("{}\n## {}\n\n", text, loc!("member-export-transfers-name"));
+            for transfer in self.score_transfers.iter() {
+                text = format!("{}- {}\n", text, transfer.line(self.id));
+            }
+        }
+
+        text
+    }
+}
+
+pub async fn erase_member(author: UserId, member: UserId, delete: bool) -> usize {
+    let mut scrubbed = 0;
+
+    let mut task_man = TASKMANAGER.write().await;
+    let ids: Vec<u32> = task_man.get_all().iter().map(|task| task.id).collect();
+    for id in ids.iter() {
+        if let Some(task) = task_man.get_mut(*id) {
+            if task.scrub_member(member).await {
+                scrubbed += 1;
+            }
+        }
+    }
+    drop(task_man);
+
+    let replacement = MEMBERSMANAGER.write().await.erase(member, delete).await;
+
+    let mut transcripts = 0;
+    for id in ids {
+        if let Some(mut transcript) = TaskTranscript::load(id) {
+            if transcript.replace_user(member, replacement) {
+                transcript.write().await;
+                transcripts += 1;
+            }
+        }
+    }
+
+    let into = match delete {
+        true => None,
+        false => Some(replacement),
+    };
+    let seasons = SEASONMANAGER.write().await.replace_user(member, into).await;
+    DIGESTSTATE.write().await.replace_user(member).await;
+
+    Logger::high(
+        "member.erase_member",
+        &format!(
+            "user {} {} member {} as {}, id scrubbed from {} tasks, {} transcripts and {} seasons",
+            author.get(),
+            match delete {
+                true => "deleted",
+                false => "anonymized",
+            },
+            member.get(),
+            replacement.get(),
+            scrubbed,
+            transcripts,
+            seasons
+        ),
+    )
+    .await;
+
+    Logger::notify(
+        "member.erase_member",
+        &loc!(
+            match delete {
+                true => "member-erase-delete-notify",
+                false => "member-erase-anonymize-notify",
+            },
+            "member" = member.get(),
+            "tasks" = scrubbed
+        ),
+    )
+    .await;
+
+    scrubbed
+}
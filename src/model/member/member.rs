@@ -1,6 +1,13 @@
 use crate::{
     connect::*,
-    model::task::{Task, TASKMANAGER},
+    model::{
+        achievement::ActivityStreak,
+        leaderboard::Leaderboard,
+        member::{Application, CkeyLink, MemberMerge, ProfileView, ScoreTransfer, WarnAppeal},
+        project::ProjectManager,
+        season::SeasonEnd,
+        task::{BulkOperation, Task, TaskManager, TASKMANAGER},
+    },
     prelude::*,
     shop::ShopData,
 };
@@ -9,9 +16,7 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_json;
 use serenity::{
     all::{ForumTagId, MessageId, RoleId},
-    builder::CreateEmbed,
-    client::Context,
-    model::{colour::Colour, guild::Member, id::UserId, timestamp::Timestamp},
+    model::{guild::Member, id::UserId, timestamp::Timestamp},
 };
 use std::collections::HashMap;
 use std::fs;
@@ -19,6 +24,10 @@ use std::sync::Arc;
 use tokio::sync::RwLock;
 use walkdir::WalkDir;
 
+pub fn erased_user() -> UserId {
+    UserId::new(1)
+}
+
 pub static MEMBERSMANAGER: Lazy<Arc<RwLock<MembersManager>>> =
     Lazy::new(|| Arc::new(RwLock::new(MembersManager::new())));
 
@@ -63,7 +72,7 @@ impl MembersManager {
                 continue;
             }
 
-            let member: ProjectMember = match serde_json::from_str(content.as_str()) {
+            let mut member: ProjectMember = match serde_json::from_str(content.as_str()) {
                 Ok(c) => c,
                 Err(e) => {
                     Logger::error(
@@ -79,12 +88,62 @@ impl MembersManager {
                 }
             };
 
+            if member.migrate_ckey_note() {
+                member.update().await;
+
+                Logger::debug(
+                    "mem_man.init",
+                    &format!("migrated ckey note of member {}", member.id.get()),
+                )
+                .await;
+            }
+
+            if member.migrate_project_scores() {
+                member.update().await;
+
+                Logger::debug(
+                    "mem_man.init",
+                    &format!(
+                        "migrated score of member {} into project wallets",
+                        member.id.get()
+                    ),
+                )
+                .await;
+            }
+
             self.members.insert(member.id.clone(), member);
         }
 
         Logger::debug("mem_man.init", "initialized from databases/members/*").await;
     }
 
+    pub async fn backfill_score_history(&mut self, task_man: &TaskManager) {
+        let mut backfilled = 0;
+
+        for member in self.members.values_mut() {
+            let id = member.id;
+            let task_score = |task: u32, mentor: bool| {
+                task_man
+                    .get(task)
+                    .and_then(|task| task.close_results.get(&id).cloned())
+                    .unwrap_or(if mentor { 2 } else { 1 })
+            };
+
+            if member.backfill_score_history(task_score) {
+                member.update().await;
+                backfilled += 1;
+            }
+        }
+
+        if backfilled > 0 {
+            Logger::debug(
+                "mem_man.backfill_score_history",
+                &format!("backfilled score history of {} members", backfilled),
+            )
+            .await;
+        }
+    }
+
     pub async fn get(&mut self, id: UserId) -> Result<&ProjectMember, serenity::Error> {
         Ok(self.members.entry(id.clone()).or_insert_with({
             let member = ProjectMember::new(id).await?;
@@ -99,6 +158,68 @@ impl MembersManager {
         }))
     }
 
+    pub fn find(&self, id: UserId) -> Option<&ProjectMember> {
+        self.members.get(&id)
+    }
+
+    pub fn get_all(&self) -> Vec<&ProjectMember> {
+        self.members.values().collect()
+    }
+
+    pub async fn replace_user(&mut self, from: UserId, into: UserId) {
+        for member in self.members.values_mut() {
+            if member.replace_user(from, into) {
+                member.update().await;
+            }
+        }
+    }
+
+    fn pseudonym(&self) -> UserId {
+        (2..)
+            .map(UserId::new)
+            .find(|id| !self.members.contains_key(id))
+            .unwrap()
+    }
+
+    pub async fn erase(&mut self, id: UserId, delete: bool) -> UserId {
+        let replacement = match delete {
+            true => erased_user(),
+            false => self.pseudonym(),
+        };
+        let member = self.members.remove(&id);
+
+        let path = DATA_PATH.join(format!("databases/members/{}", id.get()));
+        if fs::exists(&path).unwrap_or(false) {
+            if let Err(e) = fs::remove_file(&path) {
+                Logger::error(
+                    "mem_man.erase",
+                    &format!("cannot delete member data file {}: {}", id.get(), e),
+                )
+                .await;
+            }
+        }
+
+        self.replace_user(id, replacement).await;
+
+        if let (false, Some(mut member)) = (delete, member) {
+            member.id = replacement;
+            member.in_tasks = HashMap::new();
+            member.own_folder = HashMap::new();
+            member.last_activity = HashMap::new();
+            member.notes = Vec::new();
+            member.warns = Vec::new();
+            member.warn_appeals = Vec::new();
+            member.ckey = None;
+            member.inactivity_warnings = HashMap::new();
+            member.score_transfers = Vec::new();
+            member.application = None;
+            member.update().await;
+            self.members.insert(replacement, member);
+        }
+
+        replacement
+    }
+
     pub fn get_by_folder(&mut self, db: String, folder: &String) -> Option<&UserId> {
         for (id, member) in self.members.iter() {
             if let Some(f) = member.own_folder.get(&db) {
@@ -175,11 +296,31 @@ pub struct ProjectMember {
     #[serde(default)]
     pub all_time_score: i64,
     #[serde(default)]
+    pub project_scores: HashMap<String, i64>,
+    #[serde(default)]
+    pub scores_migrated: bool,
+    #[serde(default)]
+    pub score_history: Vec<(Timestamp, i64)>,
+    #[serde(default)]
     pub last_activity: HashMap<String, Timestamp>,
     #[serde(default)]
     pub warns: Vec<NotesHistory>,
     #[serde(default)]
     pub notes: Vec<NotesHistory>,
+    #[serde(default)]
+    pub ckey: Option<CkeyLink>,
+    #[serde(default)]
+    pub inactivity_warnings: HashMap<String, Timestamp>,
+    #[serde(default)]
+    pub warn_appeals: Vec<WarnAppeal>,
+    #[serde(default)]
+    pub achievements: HashMap<String, Timestamp>,
+    #[serde(default)]
+    pub activity_streak: Option<ActivityStreak>,
+    #[serde(default)]
+    pub score_transfers: Vec<ScoreTransfer>,
+    #[serde(default)]
+    pub application: Option<Application>,
     #[serde(default, skip_serializing)]
     pub shop_data: ShopData,
     #[serde(default, skip_serializing)]
@@ -196,6 +337,22 @@ pub struct ProjectMember {
     pub changed_role: Option<RoleId>,
     #[serde(default, skip_serializing)]
     pub changed_db: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub changed_warn: Option<Timestamp>,
+    #[serde(default, skip_serializing)]
+    pub bulk_operation: Option<BulkOperation>,
+    #[serde(default, skip_serializing)]
+    pub leaderboard_view: Option<(Leaderboard, usize)>,
+    #[serde(default, skip_serializing)]
+    pub season_end: Option<SeasonEnd>,
+    #[serde(default, skip_serializing)]
+    pub member_merge: Option<MemberMerge>,
+    #[serde(default, skip_serializing)]
+    pub onboarding_project: Option<String>,
+    #[serde(default, skip_serializing)]
+    pub changed_application: Option<UserId>,
+    #[serde(default, skip_serializing)]
+    pub profile_view: Option<ProfileView>,
 }
 
 impl ProjectMember {
@@ -211,9 +368,19 @@ impl ProjectMember {
                 own_folder: HashMap::new(),
                 score: 0,
                 all_time_score: 0,
+                project_scores: HashMap::new(),
+                scores_migrated: true,
+                score_history: Vec::new(),
                 last_activity: HashMap::new(),
                 warns: Vec::new(),
                 notes: Vec::new(),
+                ckey: None,
+                inactivity_warnings: HashMap::new(),
+                warn_appeals: Vec::new(),
+                achievements: HashMap::new(),
+                activity_streak: None,
+                score_transfers: Vec::new(),
+                application: None,
                 shop_data: ShopData::default(),
                 changed_member: None,
                 changed_task: None,
@@ -222,6 +389,14 @@ impl ProjectMember {
                 changed_sub_post: None,
                 changed_role: None,
                 changed_db: None,
+                changed_warn: None,
+                bulk_operation: None,
+                leaderboard_view: None,
+                season_end: None,
+                member_merge: None,
+                onboarding_project: None,
+                changed_application: None,
+                profile_view: None,
             },
             _ => serde_json::from_str(&content)?,
         })
@@ -246,14 +421,40 @@ impl ProjectMember {
 
     pub async fn update(&self) {
         self.serialize().await;
+        ProjectManager::request_stat_update();
+    }
+
+    pub fn replace_user(&mut self, from: UserId, into: UserId) -> bool {
+        let mut found = false;
+
+        for transfer in self.score_transfers.iter_mut() {
+            for id in [&mut transfer.from, &mut transfer.to] {
+                if *id == from {
+                    *id = into;
+                    found = true;
+                }
+            }
+        }
+
+        for note in self.notes.iter_mut().chain(self.warns.iter_mut()) {
+            if let NotesHistory::Current((author, _, _)) = note {
+                if *author == from {
+                    *author = into;
+                    found = true;
+                }
+            }
+        }
+
+        found
     }
 
     pub async fn change_score(&mut self, score: i64) {
-        self.score += score;
-        if score > 0 {
-            self.all_time_score += score;
+        match score > 0 {
+            true => self.credit(score),
+            false => self.withdraw(-score),
         }
         self.update().await;
+        self.check_achievements().await;
 
         if let Ok(dis_member) = self.member().await {
             Logger::low(
@@ -268,6 +469,28 @@ impl ProjectMember {
         }
     }
 
+    pub async fn decay_score(&mut self, factor: f64) {
+        let old_score = self.total_score();
+
+        self.score = (self.score as f64 * factor).round() as i64;
+        for score in self.project_scores.values_mut() {
+            *score = (*score as f64 * factor).round() as i64;
+        }
+        self.record_score();
+        self.update().await;
+
+        Logger::medium(
+            "member.decay_score",
+            &format!(
+                "score of member {} decayed from {} to {}",
+                self.id.get(),
+                old_score,
+                self.total_score()
+            ),
+        )
+        .await;
+    }
+
     pub async fn change_folder(
         &mut self,
         db: String,
@@ -393,6 +616,7 @@ impl ProjectMember {
 
         self.update_last_activity(&project_name).await;
         self.update().await;
+        self.check_achievements().await;
 
         if let Ok(dis_member) = self.member().await {
             Logger::debug(
@@ -452,6 +676,7 @@ impl ProjectMember {
 
         self.update_last_activity(&project_name).await;
         self.update().await;
+        self.check_achievements().await;
 
         if let Ok(dis_member) = self.member().await {
             Logger::debug(
@@ -674,6 +899,7 @@ impl ProjectMember {
     pub async fn update_last_activity(&mut self, project_name: &String) {
         self.last_activity
             .insert(project_name.clone(), Timestamp::now());
+        ActivityStreak::touch(&mut self.activity_streak, Timestamp::now());
         self.update().await;
 
         Logger::debug(
@@ -682,371 +908,6 @@ impl ProjectMember {
         )
         .await;
     }
-
-    pub fn to_project_stat(
-        &self,
-        member_name: String,
-        project_name: &String,
-    ) -> Result<(String, String, bool), String> {
-        Ok((
-            member_name,
-            format!(
-                r#"
-                ╠︎ **{}:** {}
-                ╠︎ **{}:** {}
-                ╠︎ **{}:** {}
-                ╠︎ **{}:** {}
-                ╚ **{}:** {}
-                "#,
-                loc!("member-project-stat-done-tasks-name"),
-                match self.done_tasks.get(project_name) {
-                    Some(tasks) => tasks.len(),
-                    None => 0,
-                },
-                loc!("member-project-stat-mentor-tasks-name"),
-                match self.mentor_tasks.get(project_name) {
-                    Some(tasks) => tasks.len(),
-                    None => 0,
-                },
-                loc!("member-project-stat-in-tasks-name"),
-                match self.in_tasks.get(project_name) {
-                    Some(tasks) => {
-                        let mut value = String::new();
-                        let task_man = TASKMANAGER
-                            .try_read()
-                            .map_err(|e| format!("cannot lock TASKMANAGER, {}", e.to_string()))?;
-
-                        for task in tasks.iter() {
-                            value = format!(
-                                "{}\n╠︎ <#{}>",
-                                value,
-                                task_man.get(*task).unwrap().thread_id.get()
-                            );
-                        }
-                        value
-                    }
-                    None => loc!("member-project-stat-no-in-tasks"),
-                },
-                loc!("member-project-stat-last-activity-name"),
-                match self.last_activity.get(project_name) {
-                    Some(activity) => format!("<t:{}:R>", activity.timestamp()),
-                    None => loc!("member-project-stat-no-last-activity"),
-                },
-                loc!("member-project-stat-score-name"),
-                self.score,
-            ),
-            true,
-        ))
-    }
-
-    pub async fn to_embed(&self, ctx: &Context, show_secret: bool) -> CreateEmbed {
-        let dis_member = self.member().await.unwrap();
-
-        let mut embed = CreateEmbed::new()
-            .title(loc!(
-                "member-stat-embed-title",
-                "member" = dis_member.display_name()
-            ))
-            .color(match get_guild().to_guild_cached(&ctx.cache) {
-                Some(guild) => match guild.member_highest_role(&dis_member) {
-                    Some(color) => color.colour,
-                    None => Colour::LIGHT_GREY,
-                },
-                None => Colour::LIGHT_GREY,
-            });
-
-        let task_man = TASKMANAGER.read().await;
-        if !self.in_tasks.is_empty() {
-            embed = embed.field(
-                loc!(
-                    "member-stat-embed-in-tasks-name",
-                    "num" = self.in_tasks.len()
-                ),
-                {
-                    let mut value = String::new();
-                    for (proj, tasks) in self.in_tasks.iter() {
-                        if !tasks.is_empty() {
-                            value = format!("{}**{} ({}):**\n", value, proj, tasks.len());
-
-                            for id in tasks.iter() {
-                                if let Some(task) = task_man.get(*id) {
-                                    value = format!(
-                                        "{}{} <#{}>\n",
-                                        value,
-                                        match id == tasks.last().unwrap_or(&0) {
-                                            false => "╠︎",
-                                            true => "╚",
-                                        },
-                                        task.thread_id.get()
-                                    );
-                                }
-                            }
-                        }
-                    }
-
-                    value
-                        .chars()
-                        .rev()
-                        .take(1024)
-                        .collect::<String>()
-                        .chars()
-                        .rev()
-                        .collect::<String>()
-                },
-                false,
-            );
-        }
-
-        if !self.done_tasks.is_empty() {
-            embed = embed.field(
-                loc!(
-                    "member-stat-embed-done-tasks-name",
-                    "num" = self.done_tasks.len()
-                ),
-                {
-                    let mut value = String::new();
-                    for (proj, tasks) in self.done_tasks.iter() {
-                        if !tasks.is_empty() {
-                            value = format!("{}**{} ({})**\n", value, proj, tasks.len());
-
-                            for task in tasks.iter() {
-                                value = format!(
-                                    "{}{} {}\n",
-                                    value,
-                                    match task == tasks.last().unwrap() {
-                                        false => "╠︎",
-                                        true => "╚",
-                                    },
-                                    match task {
-                                        TaskHistory::Current(map) => {
-                                            let mut value_2 = String::new();
-                                            for (time, id) in map.iter() {
-                                                value_2 = format!(
-                                                    "{}<t:{}:D> <#{}>\n",
-                                                    value_2,
-                                                    time.timestamp(),
-                                                    task_man.get(*id).unwrap().thread_id.get()
-                                                );
-                                            }
-                                            value_2
-                                        }
-                                        TaskHistory::OldFormat(string) => string.clone(),
-                                    }
-                                )
-                            }
-                        }
-                    }
-
-                    value
-                        .chars()
-                        .rev()
-                        .take(1024)
-                        .collect::<String>()
-                        .chars()
-                        .rev()
-                        .collect::<String>()
-                },
-                false,
-            );
-        }
-
-        if !self.mentor_tasks.is_empty() {
-            embed = embed.field(
-                loc!(
-                    "member-stat-embed-mentor-tasks-name",
-                    "num" = self.mentor_tasks.len()
-                ),
-                {
-                    let mut value = String::new();
-                    for (proj, tasks) in self.mentor_tasks.iter() {
-                        if !tasks.is_empty() {
-                            value = format!("{}**{} ({})**\n", value, proj, tasks.len());
-
-                            for task in tasks.iter() {
-                                value = format!(
-                                    "{}{} {}\n",
-                                    value,
-                                    match task == tasks.last().unwrap() {
-                                        false => "╠︎",
-                                        true => "╚",
-                                    },
-                                    match task {
-                                        TaskHistory::Current(map) => {
-                                            let mut value_2 = String::new();
-                                            for (time, id) in map.iter() {
-                                                value_2 = format!(
-                                                    "{}<t:{}:D> <#{}>\n",
-                                                    value_2,
-                                                    time.timestamp(),
-                                                    task_man.get(*id).unwrap().thread_id.get()
-                                                );
-                                            }
-                                            value_2
-                                        }
-                                        TaskHistory::OldFormat(string) => string.clone(),
-                                    }
-                                )
-                            }
-                        }
-                    }
-
-                    value
-                        .chars()
-                        .rev()
-                        .take(1024)
-                        .collect::<String>()
-                        .chars()
-                        .rev()
-                        .collect::<String>()
-                },
-                false,
-            );
-        }
-
-        let mut folder_text = String::new();
-
-        for (db, folder) in self.own_folder.iter() {
-            if let Some(f) = folder {
-                folder_text = format!("{}**{}**: `{}`\n", folder_text, db, f);
-            }
-        }
-
-        if !folder_text.is_empty() {
-            embed = embed.field(loc!("member-stat-embed-folder-name"), folder_text, false);
-        }
-
-        embed = embed
-            .field(
-                loc!("member-stat-embed-score-name"),
-                format!("`{}`", self.score),
-                false,
-            )
-            .field(
-                loc!("member-stat-embed-all-time-score-name"),
-                format!("`{}`", self.all_time_score),
-                false,
-            );
-
-        if show_secret {
-            if !self.last_activity.is_empty() {
-                embed = embed.field(
-                    loc!("member-stat-embed-last-activity-name"),
-                    {
-                        let mut value = String::new();
-                        let mut i = 0;
-
-                        for (proj, time) in &self.last_activity {
-                            i += 1;
-                            value = format!(
-                                "{}{} **{}**: <t:{}:R>\n",
-                                value,
-                                match i == self.last_activity.len() {
-                                    false => "╠︎",
-                                    true => "╚",
-                                },
-                                proj,
-                                time.timestamp()
-                            );
-                        }
-
-                        value
-                            .chars()
-                            .rev()
-                            .take(1024)
-                            .collect::<String>()
-                            .chars()
-                            .rev()
-                            .collect::<String>()
-                    },
-                    false,
-                );
-            }
-
-            if !self.notes.is_empty() {
-                embed = embed.field(
-                    loc!("member-stat-embed-notes-name", "num" = self.notes.len()),
-                    {
-                        let mut value = String::new();
-
-                        for note in &self.notes {
-                            value = format!(
-                                "{}{} {}\n",
-                                value,
-                                match note == self.notes.last().unwrap() {
-                                    false => "╠︎",
-                                    true => "╚",
-                                },
-                                match note {
-                                    NotesHistory::OldFormat(string) => string.clone(),
-                                    NotesHistory::Current((user, time, string)) => {
-                                        format!(
-                                            "<@{}> <t:{}:D>: {}",
-                                            user.get(),
-                                            time.timestamp(),
-                                            string
-                                        )
-                                    }
-                                }
-                            );
-                        }
-
-                        value
-                            .chars()
-                            .rev()
-                            .take(1024)
-                            .collect::<String>()
-                            .chars()
-                            .rev()
-                            .collect::<String>()
-                    },
-                    false,
-                );
-            }
-
-            if !self.warns.is_empty() {
-                embed = embed.field(
-                    loc!("member-stat-embed-warns-name", "num" = self.warns.len()),
-                    {
-                        let mut value = String::new();
-
-                        for warn in &self.warns {
-                            value = format!(
-                                "{}{} {}\n",
-                                value,
-                                match warn == self.warns.last().unwrap() {
-                                    false => "╠︎",
-                                    true => "╚",
-                                },
-                                match warn {
-                                    NotesHistory::OldFormat(string) => string.clone(),
-                                    NotesHistory::Current((user, time, string)) => {
-                                        format!(
-                                            "<@{}> <t:{}:D>: {}",
-                                            user.get(),
-                                            time.timestamp(),
-                                            string
-                                        )
-                                    }
-                                }
-                            );
-                        }
-
-                        value
-                            .chars()
-                            .rev()
-                            .take(1024)
-                            .collect::<String>()
-                            .chars()
-                            .rev()
-                            .collect::<String>()
-                    },
-                    false,
-                );
-            }
-        }
-
-        embed
-    }
 }
 
 fn deserialize_old_or_new_folder<'de, D>(
@@ -0,0 +1,321 @@
+use crate::{
+    model::{
+        member::{merge_score_history, ProjectMember, MEMBERSMANAGER},
+        task::TASKMANAGER,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed},
+    model::id::UserId,
+};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct MemberMerge {
+    pub from: UserId,
+    pub into: UserId,
+}
+
+impl ProjectMember {
+    pub fn absorb(&mut self, other: ProjectMember) {
+        self.score += other.score;
+        for (project, score) in other.project_scores {
+            *self.project_scores.entry(project).or_default() += score;
+        }
+        self.all_time_score += other.all_time_score;
+        self.score_history = merge_score_history(&self.score_history, &other.score_history);
+
+        for (project, tasks) in other.in_tasks {
+            let in_tasks = self.in_tasks.entry(project).or_default();
+            for task in tasks {
+                if !in_tasks.contains(&task) {
+                    in_tasks.push(task);
+                }
+            }
+        }
+
+        for (project, history) in other.done_tasks {
+            self.done_tasks.entry(project).or_default().extend(history);
+        }
+
+        for (project, history) in other.mentor_tasks {
+            self.mentor_tasks
+                .entry(project)
+                .or_default()
+                .extend(history);
+        }
+
+        for (db, folder) in other.own_folder {
+            if !matches!(self.own_folder.get(&db), Some(Some(_))) {
+                self.own_folder.insert(db, folder);
+            }
+        }
+
+        for (project, time) in other.last_activity {
+            let last = self.last_activity.entry(project).or_insert(time);
+            if last.unix_timestamp() < time.unix_timestamp() {
+                *last = time;
+            }
+        }
+
+        for (id, time) in other.achievements {
+            let unlocked = self.achievements.entry(id).or_insert(time);
+            if unlocked.unix_timestamp() > time.unix_timestamp() {
+                *unlocked = time;
+            }
+        }
+
+        if let Some(streak) = other.activity_streak {
+            if self
+                .activity_streak
+                .as_ref()
+                .is_none_or(|x| x.last.unix_timestamp() < streak.last.unix_timestamp())
+            {
+                self.activity_streak = Some(streak);
+            }
+        }
+
+        if self.ckey.is_none() {
+            self.ckey = other.ckey;
+        }
+
+        self.notes.extend(other.notes);
+        self.warns.extend(other.warns);
+        self.warn_appeals.extend(other.warn_appeals);
+        self.score_transfers.extend(other.score_transfers);
+        self.record_score();
+    }
+}
+
+impl MemberMerge {
+    pub async fn preview_embed(&self) -> CreateEmbed {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let from = mem_man.get(self.from).await.unwrap().clone();
+        let into = mem_man.get(self.into).await.unwrap().clone();
+        drop(mem_man);
+
+        let tasks = TASKMANAGER
+            .read()
+            .await
+            .get_all()
+            .iter()
+            .filter(|task| {
+                task.members.get().contains(&self.from) || task.mentor_id.get() == &Some(self.from)
+            })
+            .count();
+
+        let count = |member: &ProjectMember| {
+            (
+                member.done_tasks.values().map(|x| x.len()).sum::<usize>(),
+                member.mentor_tasks.values().map(|x| x.len()).sum::<usize>(),
+            )
+        };
+        let (from_done, from_mentor) = count(&from);
+        let (into_done, into_mentor) = count(&into);
+
+        let mut folders = Vec::new();
+        for (db, folder) in from.own_folder.iter() {
+            let Some(folder) = folder else {
+                continue;
+            };
+
+            folders.push(match into.own_folder.get(db) {
+                Some(Some(kept)) if kept != folder => loc!(
+                    "member-merge-folder-conflict",
+                    "db" = db,
+                    "kept" = kept,
+                    "dropped" = folder
+                ),
+                Some(Some(_)) => continue,
+                _ => loc!("member-merge-folder-moved", "db" = db, "folder" = folder),
+            });
+        }
+
+        let mut embed = CreateEmbed::new()
+            .title(loc!("member-merge-preview-title"))
+            .color(Colour::ORANGE)
+            .description(loc!(
+                "member-merge-preview-description",
+                "from" = self.from.get(),
+                "into" = self.into.get()
+            ))
+            .field(
+                loc!("member-stat-embed-score-name"),
+                format!(
+                    "`{}` + `{}` = `{}`",
+                    into.total_score(),
+                    from.total_score(),
+                    into.total_score() + from.total_score()
+                ),
+                true,
+            )
+            .field(
+                loc!("member-stat-embed-all-time-score-name"),
+                format!(
+                    "`{}` + `{}` = `{}`",
+                    into.all_time_score,
+                    from.all_time_score,
+                    into.all_time_score + from.all_time_score
+                ),
+                true,
+            )
+            .field(
+                loc!("member-merge-history-name"),
+                box_list(Vec::from([
+                    loc!(
+                        "member-merge-done-tasks",
+                        "into" = into_done,
+                        "from" = from_done
+                    ),
+                    loc!(
+                        "member-merge-mentor-tasks",
+                        "into" = into_mentor,
+                        "from" = from_mentor
+                    ),
+                    loc!(
+                        "member-merge-notes",
+                        "into" = into.notes.len(),
+                        "from" = from.notes.len()
+                    ),
+                    loc!(
+                        "member-merge-warns",
+                        "into" = into.warns.len(),
+                        "from" = from.warns.len()
+                    ),
+                    loc!("member-merge-tasks-rewrite", "num" = tasks),
+                ])),
+                false,
+            );
+
+        if !folders.is_empty() {
+            embed = embed.field(
+                loc!("member-stat-embed-folder-name"),
+                truncate_text(box_list(folders), 1024),
+                false,
+            );
+        }
+
+        embed
+    }
+
+    pub async fn apply(&self) -> usize {
+        let mut rewritten = 0;
+
+        let mut task_man = TASKMANAGER.write().await;
+        for id in task_man
+            .get_all()
+            .iter()
+            .map(|task| task.id)
+            .collect::<Vec<u32>>()
+        {
+            if let Some(task) = task_man.get_mut(id) {
+                if task.replace_member(self.from, self.into).await {
+                    rewritten += 1;
+                }
+            }
+        }
+        drop(task_man);
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let from = mem_man.get(self.from).await.unwrap().clone();
+        let into = mem_man.get_mut(self.into).await.unwrap();
+
+        into.absorb(from);
+        into.update().await;
+        mem_man.replace_user(self.from, self.into).await;
+        mem_man.erase(self.from, true).await;
+        drop(mem_man);
+
+        Logger::high(
+            "member_merge.apply",
+            &format!(
+                "member {} merged into member {}, id rewritten in {} tasks",
+                self.from.get(),
+                self.into.get(),
+                rewritten
+            ),
+        )
+        .await;
+
+        rewritten
+    }
+
+    pub fn confirm_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("member-merge:confirm")
+                .label(loc!("member-merge-confirm-button"))
+                .style(ButtonStyle::Danger),
+            CreateButton::new("member-merge:cancel")
+                .label(loc!("member-merge-cancel-button"))
+                .style(ButtonStyle::Secondary),
+        ]))])
+    }
+}
+
+pub async fn member_merge_listener() {
+    #[listen_component("member-merge:confirm")]
+    async fn confirm(ctx: &Context, inter: ComponentInteraction) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let merge = MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .member_merge
+            .take();
+
+        let Some(merge) = merge else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("member-merge-no-operation")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let tasks = merge.apply().await;
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().content(loc!(
+                    "member-merge-done",
+                    "from" = merge.from.get(),
+                    "into" = merge.into.get(),
+                    "tasks" = tasks
+                )),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("member-merge:cancel")]
+    async fn cancel(ctx: &Context, inter: ComponentInteraction) {
+        MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .member_merge = None;
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("member-merge-cancelled"))
+                        .embeds(Vec::new())
+                        .components(Vec::new()),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -1,6 +1,24 @@
 mod changer_listen;
+mod ckey;
+mod export;
 mod member;
 mod member_changer;
+mod merge;
+mod onboarding;
+mod profile;
+mod transfer;
+mod wallet;
+mod warn;
 
 pub use changer_listen::*;
-pub use member::{NotesHistory, ProjectMember, TaskHistory, MEMBERSMANAGER};
+pub use ckey::*;
+pub use export::*;
+pub use member::{
+    erased_user, MembersManager, NotesHistory, ProjectMember, TaskHistory, MEMBERSMANAGER,
+};
+pub use merge::*;
+pub use onboarding::*;
+pub use profile::*;
+pub use transfer::*;
+pub use wallet::*;
+pub use warn::*;
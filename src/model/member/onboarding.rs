@@ -0,0 +1,752 @@
+use crate::{
+    model::{
+        member::{ProjectMember, MEMBERSMANAGER},
+        project::PROJECTMANAGER,
+        role::ROLEMANAGER,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use serenity::{
+    self,
+    all::{
+        ActionRowComponent, ButtonStyle, Colour, ComponentInteractionDataKind, CreateActionRow,
+        CreateButton, CreateEmbed, CreateInputText, CreateModal, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption, InputTextStyle,
+    },
+    model::{
+        guild::Member,
+        id::{ChannelId, MessageId, UserId},
+        timestamp::Timestamp,
+    },
+};
+
+const MAX_QUESTIONS: usize = 5;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct OnboardingConfig {
+    #[serde(default)]
+    pub welcome_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub review_channel: Option<ChannelId>,
+    #[serde(default, deserialize_with = "deserialize_questions")]
+    pub questions: Vec<OnboardingQuestion>,
+    #[serde(default)]
+    pub folder_question: Option<String>,
+    #[serde(default)]
+    pub remove_guest_role: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct OnboardingQuestion {
+    pub id: String,
+    pub label: String,
+    #[serde(default)]
+    pub placeholder: Option<String>,
+    #[serde(default)]
+    pub paragraph: bool,
+    #[serde(default = "default_required")]
+    pub required: bool,
+}
+
+fn default_required() -> bool {
+    true
+}
+
+fn deserialize_questions<'de, D>(deserializer: D) -> Result<Vec<OnboardingQuestion>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let questions = Vec::<OnboardingQuestion>::deserialize(deserializer)?;
+
+    if questions.len() > MAX_QUESTIONS {
+        return Err(serde::de::Error::custom(format!(
+            "onboarding form can have at most {} questions, got {}",
+            MAX_QUESTIONS,
+            questions.len()
+        )));
+    }
+
+    Ok(questions)
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum ApplicationState {
+    Pending,
+    Approved(UserId),
+    Rejected(UserId, String),
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Application {
+    pub project: Option<String>,
+    pub answers: Vec<(String, String)>,
+    pub created: Timestamp,
+    pub message: Option<MessageId>,
+    pub state: ApplicationState,
+}
+
+impl OnboardingConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.review_channel.is_some() && !self.questions.is_empty()
+    }
+
+    fn modal(&self) -> CreateModal {
+        CreateModal::new("onboarding:form", loc!("onboarding-modal-title")).components(
+            self.questions
+                .iter()
+                .map(|question| {
+                    let mut input = CreateInputText::new(
+                        match question.paragraph {
+                            true => InputTextStyle::Paragraph,
+                            false => InputTextStyle::Short,
+                        },
+                        truncate_text(loc!(&question.label), 45),
+                        format!("onboarding:answer:{}", question.id),
+                    )
+                    .required(question.required);
+
+                    if let Some(placeholder) = &question.placeholder {
+                        input = input.placeholder(truncate_text(loc!(placeholder), 100));
+                    }
+
+                    CreateActionRow::InputText(input)
+                })
+                .collect(),
+        )
+    }
+}
+
+impl Application {
+    fn to_embed(&self, member: UserId, questions: &[OnboardingQuestion]) -> CreateEmbed {
+        let mut embed = CreateEmbed::new()
+            .title(loc!("onboarding-application-title"))
+            .color(match self.state {
+                ApplicationState::Pending => Colour::ORANGE,
+                ApplicationState::Approved(_) => Colour::DARK_GREEN,
+                ApplicationState::Rejected(_, _) => Colour::RED,
+            })
+            .description(loc!(
+                "onboarding-application-description",
+                "member" = member.get(),
+                "time" = self.created.unix_timestamp()
+            ));
+
+        if let Some(project) = &self.project {
+            embed = embed.field(loc!("onboarding-application-project-name"), project, false);
+        }
+
+        for (id, answer) in self.answers.iter() {
+            let label = questions
+                .iter()
+                .find(|x| &x.id == id)
+                .map(|x| loc!(&x.label))
+                .unwrap_or(id.clone());
+
+            embed = embed.field(
+                truncate_text(label, 256),
+                match answer.is_empty() {
+                    true => "—".to_string(),
+                    false => truncate_text(answer.clone(), 1024),
+                },
+                false,
+            );
+        }
+
+        match &self.state {
+            ApplicationState::Pending => (),
+            ApplicationState::Approved(user) => {
+                embed = embed.field(
+                    loc!("onboarding-application-result-name"),
+                    loc!("onboarding-approved-by", "user" = user.get()),
+                    false,
+                );
+            }
+            ApplicationState::Rejected(user, reason) => {
+                embed = embed.field(
+                    loc!("onboarding-application-result-name"),
+                    truncate_text(
+                        loc!(
+                            "onboarding-rejected-by",
+                            "user" = user.get(),
+                            "reason" = reason
+                        ),
+                        1024,
+                    ),
+                    false,
+                );
+            }
+        }
+
+        embed
+    }
+
+    fn review_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("onboarding:approve")
+                .label(loc!("onboarding-approve-button"))
+                .style(ButtonStyle::Success),
+            CreateButton::new("onboarding:reject")
+                .label(loc!("onboarding-reject-button"))
+                .style(ButtonStyle::Danger),
+        ]))])
+    }
+}
+
+pub async fn send_welcome(ctx: &Context, member: &Member) {
+    let onboarding = CONFIG.read().await.onboarding.clone();
+
+    if !onboarding.is_enabled() {
+        return;
+    }
+
+    let message = CreateMessage::new()
+        .content(loc!("onboarding-welcome", "member" = member.user.id.get()))
+        .components(Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("onboarding:apply")
+                .label(loc!("onboarding-apply-button"))
+                .style(ButtonStyle::Primary),
+        ]))]));
+
+    let result = match onboarding.welcome_channel {
+        Some(channel) => channel.send_message(&ctx.http, message).await,
+        None => member.user.id.direct_message(&ctx.http, message).await,
+    };
+
+    if let Err(e) = result {
+        Logger::debug(
+            "onboarding.send_welcome",
+            &format!(
+                "cannot send welcome message to member {}: {}",
+                member.user.id.get(),
+                e
+            ),
+        )
+        .await;
+    }
+}
+
+async fn apply_roles(ctx: &Context, member: UserId, project: &Option<String>) {
+    let roles = match project {
+        Some(project) => PROJECTMANAGER
+            .read()
+            .await
+            .get(project)
+            .map(|x| x.associated_roles.clone())
+            .unwrap_or_default(),
+        None => Vec::new(),
+    };
+    let (guest_role, remove_guest) = {
+        let config = CONFIG.read().await;
+        (config.guest_role, config.onboarding.remove_guest_role)
+    };
+
+    let Ok(dis_member) = fetch_member(&member).await else {
+        return;
+    };
+
+    for role in roles.iter() {
+        if !dis_member.roles.contains(role) {
+            Logger::if_ok(
+                "onboarding.apply_roles",
+                "cannot add project role to approved member",
+                dis_member.add_role(&ctx.http, role).await,
+            )
+            .await;
+        }
+    }
+
+    if let (Some(guest_role), true) = (guest_role, remove_guest) {
+        if dis_member.roles.contains(&guest_role) {
+            Logger::if_ok(
+                "onboarding.apply_roles",
+                "cannot remove guest role from approved member",
+                dis_member.remove_role(&ctx.http, guest_role).await,
+            )
+            .await;
+        }
+    }
+}
+
+async fn link_answer_folder(member: UserId, folder: String) {
+    let Ok(dis_member) = fetch_member(&member).await else {
+        return;
+    };
+
+    let role_man = ROLEMANAGER.read().await;
+    let dbs = role_man.member_db_permissons(&dis_member);
+
+    if dbs.len() != 1 {
+        Logger::notify(
+            "onboarding.link_answer_folder",
+            &loc!(
+                "onboarding-folder-not-linked",
+                "member" = member.get(),
+                "folder" = folder
+            ),
+        )
+        .await;
+        return;
+    }
+
+    let db = dbs[0].clone();
+    drop(role_man);
+
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    if let Some(other) = mem_man.get_by_folder(db.clone(), &folder).cloned() {
+        drop(mem_man);
+
+        Logger::notify(
+            "onboarding.link_answer_folder",
+            &loc!(
+                "link-folder-command-stranger-folder-notify",
+                "member1" = member.get(),
+                "folder" = folder,
+                "member2" = other.get(),
+            ),
+        )
+        .await;
+        return;
+    }
+
+    if let Ok(project_member) = mem_man.get_mut(member).await {
+        if let Err(e) = project_member.change_folder(db, Some(folder)).await {
+            Logger::error(
+                "onboarding.link_answer_folder",
+                &format!("error while linking folder of new member: {:?}", e),
+            )
+            .await;
+        }
+    }
+}
+
+fn find_application(mem_man: &[&ProjectMember], message: MessageId) -> Option<UserId> {
+    mem_man
+        .iter()
+        .find(|member| {
+            member
+                .application
+                .as_ref()
+                .is_some_and(|x| x.message == Some(message) && x.state == ApplicationState::Pending)
+        })
+        .map(|member| member.id)
+}
+
+pub async fn onboarding_listener() {
+    #[listen_component("onboarding:apply")]
+    async fn apply(ctx: &Context, inter: ComponentInteraction) {
+        let onboarding = CONFIG.read().await.onboarding.clone();
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let member = mem_man.get_mut(inter.user.id).await.unwrap();
+
+        let refuse = match member.application.as_ref().map(|x| &x.state) {
+            Some(ApplicationState::Pending) => Some(loc!("onboarding-already-pending")),
+            Some(ApplicationState::Approved(_)) => Some(loc!("onboarding-already-approved")),
+            _ if !onboarding.is_enabled() => Some(loc!("onboarding-disabled")),
+            _ => None,
+        };
+
+        if let Some(content) = refuse {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(content)
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut projects: Vec<String> = PROJECTMANAGER
+            .read()
+            .await
+            .projects()
+            .into_iter()
+            .cloned()
+            .collect();
+        projects.sort();
+
+        if projects.len() > 1 {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("onboarding-project-select-content"))
+                            .components(Vec::from([CreateActionRow::SelectMenu(
+                                CreateSelectMenu::new(
+                                    "onboarding:project",
+                                    CreateSelectMenuKind::String {
+                                        options: projects
+                                            .iter()
+                                            .take(25)
+                                            .map(|x| CreateSelectMenuOption::new(x, x))
+                                            .collect(),
+                                    },
+                                )
+                                .placeholder(loc!("onboarding-project-select-placeholder")),
+                            )]))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        member.onboarding_project = projects.pop();
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(onboarding.modal()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("onboarding:project")]
+    async fn project(ctx: &Context, inter: ComponentInteraction) {
+        let ComponentInteractionDataKind::StringSelect { values } = &inter.data.kind else {
+            return;
+        };
+
+        MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .onboarding_project = values.first().cloned();
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(CONFIG.read().await.onboarding.modal()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_modal("onboarding:form")]
+    async fn form(ctx: &Context, inter: ModalInteraction) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let onboarding = CONFIG.read().await.onboarding.clone();
+
+        let Some(channel) = onboarding.review_channel else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("onboarding-disabled")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut answers = Vec::new();
+        for row in inter.data.components.iter() {
+            for comp in row.components.iter() {
+                if let ActionRowComponent::InputText(text) = comp {
+                    if let Some(id) = text.custom_id.strip_prefix("onboarding:answer:") {
+                        answers.push((
+                            id.to_string(),
+                            text.value.clone().unwrap_or_default().trim().to_string(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let member = mem_man.get_mut(inter.user.id).await.unwrap();
+
+        if member
+            .application
+            .as_ref()
+            .is_some_and(|x| x.state == ApplicationState::Pending)
+        {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("onboarding-already-pending")),
+                )
+                .await
+                .unwrap();
+            return;
+        }
+
+        let mut application = Application {
+            project: member.onboarding_project.clone(),
+            answers,
+            created: Timestamp::now(),
+            message: None,
+            state: ApplicationState::Pending,
+        };
+
+        match channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new()
+                    .embed(application.to_embed(inter.user.id, &onboarding.questions))
+                    .components(Application::review_buttons()),
+            )
+            .await
+        {
+            Ok(message) => application.message = Some(message.id),
+            Err(e) => {
+                drop(mem_man);
+                Logger::error(
+                    "onboarding.form",
+                    &format!("cannot send application to review channel: {}", e),
+                )
+                .await;
+
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("onboarding-send-error")),
+                    )
+                    .await
+                    .unwrap();
+                return;
+            }
+        }
+
+        member.onboarding_project = None;
+        member.application = Some(application);
+        member.update().await;
+        drop(mem_man);
+
+        Logger::medium(
+            "onboarding.form",
+            &format!("member {} submitted an application", inter.user.id.get()),
+        )
+        .await;
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().content(loc!("onboarding-submitted")),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("onboarding:approve")]
+    async fn approve(ctx: &Context, inter: ComponentInteraction) {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+
+        let Some(member_id) = find_application(&mem_man.get_all(), inter.message.id) else {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("onboarding-not-found"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        inter.defer(&ctx.http).await.unwrap();
+
+        let member = mem_man.get_mut(member_id).await.unwrap();
+        let application = member.application.as_mut().unwrap();
+        application.state = ApplicationState::Approved(inter.user.id);
+        let application = application.clone();
+        member.update().await;
+        drop(mem_man);
+
+        apply_roles(ctx, member_id, &application.project).await;
+
+        let onboarding = CONFIG.read().await.onboarding.clone();
+        if let Some(folder) = onboarding.folder_question.as_ref().and_then(|question| {
+            application
+                .answers
+                .iter()
+                .find(|(id, answer)| id == question && !answer.is_empty())
+                .map(|(_, answer)| answer.clone())
+        }) {
+            link_answer_folder(member_id, folder).await;
+        }
+
+        Logger::high(
+            "onboarding.approve",
+            &format!(
+                "user {} approved application of member {} to project {:?}",
+                inter.user.id.get(),
+                member_id.get(),
+                application.project
+            ),
+        )
+        .await;
+
+        if let Err(e) = member_id
+            .direct_message(
+                &ctx.http,
+                CreateMessage::new().content(loc!("onboarding-approved-dm")),
+            )
+            .await
+        {
+            Logger::debug(
+                "onboarding.approve",
+                &format!("cannot send approval to member {}: {}", member_id.get(), e),
+            )
+            .await;
+        }
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new()
+                    .embed(application.to_embed(member_id, &onboarding.questions))
+                    .components(Vec::new()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("onboarding:reject")]
+    async fn reject(ctx: &Context, inter: ComponentInteraction) {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+
+        let Some(member_id) = find_application(&mem_man.get_all(), inter.message.id) else {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("onboarding-not-found"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        mem_man
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .changed_application = Some(member_id);
+        drop(mem_man);
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(
+                    CreateModal::new("onboarding:reject", loc!("onboarding-reject-modal-title"))
+                        .components(Vec::from([CreateActionRow::InputText(
+                            CreateInputText::new(
+                                InputTextStyle::Paragraph,
+                                loc!("onboarding-reject-reason-label"),
+                                "onboarding:reject:reason",
+                            )
+                            .max_length(1000),
+                        )])),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_modal("onboarding:reject")]
+    async fn reject_reason(ctx: &Context, inter: ModalInteraction) {
+        let mut reason = String::new();
+        for row in inter.data.components.iter() {
+            for comp in row.components.iter() {
+                if let ActionRowComponent::InputText(text) = comp {
+                    reason = text.value.clone().unwrap_or_default().trim().to_string();
+                }
+            }
+        }
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let Some(member_id) = mem_man
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .changed_application
+            .take()
+        else {
+            return;
+        };
+
+        let member = mem_man.get_mut(member_id).await.unwrap();
+        let Some(application) = member
+            .application
+            .as_mut()
+            .filter(|x| x.state == ApplicationState::Pending)
+        else {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("onboarding-not-found"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        application.state = ApplicationState::Rejected(inter.user.id, reason.clone());
+        let application = application.clone();
+        member.update().await;
+        drop(mem_man);
+
+        Logger::high(
+            "onboarding.reject",
+            &format!(
+                "user {} rejected application of member {}: {}",
+                inter.user.id.get(),
+                member_id.get(),
+                reason
+            ),
+        )
+        .await;
+
+        if let Err(e) = member_id
+            .direct_message(
+                &ctx.http,
+                CreateMessage::new().content(loc!("onboarding-rejected-dm", "reason" = reason)),
+            )
+            .await
+        {
+            Logger::debug(
+                "onboarding.reject",
+                &format!("cannot send rejection to member {}: {}", member_id.get(), e),
+            )
+            .await;
+        }
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .embed(
+                            application
+                                .to_embed(member_id, &CONFIG.read().await.onboarding.questions),
+                        )
+                        .components(Vec::new()),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,586 @@
+use crate::{
+    model::{
+        achievement::ACHIEVEMENTMANAGER,
+        member::{NotesHistory, ProjectMember, TaskHistory, MEMBERSMANAGER},
+        task::{TaskManager, TASKMANAGER},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{
+        ButtonStyle, Colour, ComponentInteractionDataKind, CreateActionRow, CreateAttachment,
+        CreateButton, CreateEmbed, CreateEmbedFooter, CreateSelectMenu, CreateSelectMenuKind,
+        CreateSelectMenuOption,
+    },
+    model::{id::UserId, timestamp::Timestamp},
+};
+use std::collections::HashMap;
+
+const FIELD_LIMIT: usize = 1024;
+const EMBED_LIMIT: usize = 5000;
+const PAGE_FIELDS: usize = 6;
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum ProfileTab {
+    Overview,
+    InTasks,
+    DoneTasks,
+    MentorTasks,
+    Notes,
+    Warns,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ProfileView {
+    pub member: UserId,
+    pub tab: ProfileTab,
+    pub page: usize,
+    pub show_secret: bool,
+}
+
+impl ProfileTab {
+    fn all(show_secret: bool) -> Vec<Self> {
+        let mut tabs = Vec::from([
+            Self::Overview,
+            Self::InTasks,
+            Self::DoneTasks,
+            Self::MentorTasks,
+        ]);
+
+        if show_secret {
+            tabs.extend([Self::Notes, Self::Warns]);
+        }
+
+        tabs
+    }
+
+    fn id(&self) -> &'static str {
+        match self {
+            Self::Overview => "overview",
+            Self::InTasks => "in-tasks",
+            Self::DoneTasks => "done-tasks",
+            Self::MentorTasks => "mentor-tasks",
+            Self::Notes => "notes",
+            Self::Warns => "warns",
+        }
+    }
+
+    fn from_id(id: &str) -> Self {
+        Self::all(true)
+            .into_iter()
+            .find(|x| x.id() == id)
+            .unwrap_or(Self::Overview)
+    }
+
+    fn name(&self) -> String {
+        loc!(&format!("member-profile-tab-{}", self.id()))
+    }
+}
+
+pub fn paginate_fields(
+    sections: Vec<(String, Vec<String>)>,
+    max_fields: usize,
+) -> Vec<Vec<(String, String)>> {
+    let mut fields = Vec::new();
+
+    for (name, lines) in sections {
+        let name = truncate_text(name, 256);
+        let mut value = String::new();
+
+        for line in lines {
+            let line = truncate_text(line, FIELD_LIMIT);
+
+            if !value.is_empty() && value.chars().count() + line.chars().count() + 1 > FIELD_LIMIT {
+                fields.push((name.clone(), value));
+                value = String::new();
+            }
+
+            value = match value.is_empty() {
+                true => line,
+                false => format!("{}\n{}", value, line),
+            };
+        }
+
+        if !value.is_empty() {
+            fields.push((name, value));
+        }
+    }
+
+    split_pages(fields, max_fields, EMBED_LIMIT, |(name, value)| {
+        name.chars().count() + value.chars().count()
+    })
+}
+
+fn history_lines(task_man: &TaskManager, history: &[TaskHistory]) -> Vec<String> {
+    let mut lines = Vec::new();
+
+    for task in history.iter() {
+        match task {
+            TaskHistory::Current(map) => {
+                let mut entries: Vec<(&Timestamp, &u32)> = map.iter().collect();
+                entries.sort_by_key(|(time, _)| time.unix_timestamp());
+
+                for (time, id) in entries {
+                    lines.push(match task_man.get(*id) {
+                        Some(task) => {
+                            format!("<t:{}:D> <#{}>", time.timestamp(), task.thread_id.get())
+                        }
+                        None => format!("<t:{}:D> `{}`", time.timestamp(), id),
+                    });
+                }
+            }
+            TaskHistory::OldFormat(string) => lines.extend(
+                string
+                    .lines()
+                    .filter(|x| !x.trim().is_empty())
+                    .map(|x| x.to_string()),
+            ),
+        }
+    }
+
+    lines
+}
+
+fn history_count(tasks: &HashMap<String, Vec<TaskHistory>>) -> usize {
+    tasks
+        .values()
+        .flatten()
+        .map(|task| match task {
+            TaskHistory::Current(map) => map.len(),
+            TaskHistory::OldFormat(_) => 1,
+        })
+        .sum()
+}
+
+fn note_line(note: &NotesHistory) -> String {
+    match note {
+        NotesHistory::OldFormat(string) => string.clone(),
+        NotesHistory::Current((user, time, string)) => {
+            format!("<@{}> <t:{}:D>: {}", user.get(), time.timestamp(), string)
+        }
+    }
+}
+
+impl ProjectMember {
+    async fn profile_sections(
+        &self,
+        tab: ProfileTab,
+        show_secret: bool,
+    ) -> Vec<(String, Vec<String>)> {
+        let mut sections = Vec::new();
+
+        match tab {
+            ProfileTab::Overview => {
+                sections.push((
+                    loc!("member-stat-embed-score-name"),
+                    Vec::from([format!("`{}`", self.total_score())]),
+                ));
+
+                let mut wallets: Vec<(&String, &i64)> = self
+                    .project_scores
+                    .iter()
+                    .filter(|(_, score)| **score != 0)
+                    .collect();
+                wallets.sort();
+
+                if !wallets.is_empty() {
+                    sections.push((
+                        loc!("member-stat-embed-project-scores-name"),
+                        wallets
+                            .into_iter()
+                            .map(|(project, score)| format!("**{}**: `{}`", project, score))
+                            .collect(),
+                    ));
+                }
+                sections.push((
+                    loc!("member-stat-embed-all-time-score-name"),
+                    Vec::from([format!("`{}`", self.all_time_score)]),
+                ));
+
+                let folders: Vec<String> = self
+                    .own_folder
+                    .iter()
+                    .filter_map(|(db, folder)| {
+                        folder.as_ref().map(|f| format!("**{}**: `{}`", db, f))
+                    })
+                    .collect();
+                if !folders.is_empty() {
+                    sections.push((loc!("member-stat-embed-folder-name"), folders));
+                }
+
+                if let Some(ckey) = &self.ckey {
+                    sections.push((
+                        loc!("member-stat-embed-ckey-name"),
+                        Vec::from([ckey.describe()]),
+                    ));
+                }
+
+                sections.push((
+                    loc!("member-profile-summary-name"),
+                    Vec::from([loc!(
+                        "member-profile-summary",
+                        "in_tasks" = self.in_tasks.values().map(|x| x.len()).sum::<usize>(),
+                        "done" = history_count(&self.done_tasks),
+                        "mentor" = history_count(&self.mentor_tasks)
+                    )]),
+                ));
+
+                if !self.achievements.is_empty() {
+                    let achievement_man = ACHIEVEMENTMANAGER.read().await;
+
+                    let mut achievements: Vec<(&String, &Timestamp)> =
+                        self.achievements.iter().collect();
+                    achievements.sort_by_key(|(_, time)| time.unix_timestamp());
+
+                    sections.push((
+                        loc!(
+                            "member-stat-embed-achievements-name",
+                            "num" = achievements.len()
+                        ),
+                        achievements
+                            .iter()
+                            .filter_map(|(id, time)| {
+                                achievement_man.get(id).map(|achievement| {
+                                    format!("{} <t:{}:D>", achievement.title(), time.timestamp())
+                                })
+                            })
+                            .collect(),
+                    ));
+                }
+
+                if show_secret {
+                    if !self.last_activity.is_empty() {
+                        sections.push((
+                            loc!("member-stat-embed-last-activity-name"),
+                            self.last_activity
+                                .iter()
+                                .map(|(proj, time)| {
+                                    format!("**{}**: <t:{}:R>", proj, time.timestamp())
+                                })
+                                .collect(),
+                        ));
+                    }
+
+                    if !self.score_transfers.is_empty() {
+                        sections.push((
+                            loc!(
+                                "member-stat-embed-transfers-name",
+                                "num" = self.score_transfers.len()
+                            ),
+                            self.score_transfers
+                                .iter()
+                                .rev()
+                                .map(|x| x.line(self.id))
+                                .collect(),
+                        ));
+                    }
+                }
+            }
+            ProfileTab::InTasks => {
+                let task_man = TASKMANAGER.read().await;
+
+                for (proj, tasks) in self.in_tasks.iter() {
+                    if tasks.is_empty() {
+                        continue;
+                    }
+
+                    sections.push((
+                        format!("{} ({})", proj, tasks.len()),
+                        tasks
+                            .iter()
+                            .filter_map(|id| task_man.get(*id))
+                            .map(|task| format!("<#{}>", task.thread_id.get()))
+                            .collect(),
+                    ));
+                }
+            }
+            ProfileTab::DoneTasks | ProfileTab::MentorTasks => {
+                let task_man = TASKMANAGER.read().await;
+                let history = match tab {
+                    ProfileTab::DoneTasks => &self.done_tasks,
+                    _ => &self.mentor_tasks,
+                };
+
+                for (proj, tasks) in history.iter() {
+                    let lines = history_lines(&task_man, tasks);
+                    if lines.is_empty() {
+                        continue;
+                    }
+
+                    sections.push((format!("{} ({})", proj, lines.len()), lines));
+                }
+            }
+            ProfileTab::Notes => {
+                if show_secret && !self.notes.is_empty() {
+                    sections.push((
+                        loc!("member-stat-embed-notes-name", "num" = self.notes.len()),
+                        self.notes.iter().map(note_line).collect(),
+                    ));
+                }
+            }
+            ProfileTab::Warns => {
+                if show_secret {
+                    let policy = CONFIG.read().await.warn_policy.clone();
+
+                    for (warns, name) in [
+                        (self.active_warns(&policy), "member-stat-embed-warns-name"),
+                        (
+                            self.expired_warns(&policy),
+                            "member-stat-embed-expired-warns-name",
+                        ),
+                    ] {
+                        if warns.is_empty() {
+                            continue;
+                        }
+
+                        sections.push((
+                            loc!(name, "num" = warns.len()),
+                            warns.iter().map(|warn| self.warn_line(warn)).collect(),
+                        ));
+                    }
+                }
+            }
+        }
+
+        sections
+    }
+
+    async fn profile_header(&self, ctx: &Context) -> CreateEmbed {
+        let dis_member = self.member().await.unwrap();
+
+        CreateEmbed::new()
+            .title(loc!(
+                "member-stat-embed-title",
+                "member" = dis_member.display_name()
+            ))
+            .color(match get_guild().to_guild_cached(&ctx.cache) {
+                Some(guild) => match guild.member_highest_role(&dis_member) {
+                    Some(color) => color.colour,
+                    None => Colour::LIGHT_GREY,
+                },
+                None => Colour::LIGHT_GREY,
+            })
+    }
+
+    pub async fn to_embed(&self, ctx: &Context, show_secret: bool) -> CreateEmbed {
+        let mut sections = Vec::new();
+        for tab in ProfileTab::all(show_secret) {
+            sections.extend(self.profile_sections(tab, show_secret).await);
+        }
+
+        let pages = paginate_fields(sections, 25);
+        let mut embed = self.profile_header(ctx).await;
+
+        for (name, value) in pages[0].iter() {
+            embed = embed.field(name, value, false);
+        }
+
+        if pages.len() > 1 {
+            embed = embed.footer(CreateEmbedFooter::new(loc!("member-profile-truncated")));
+        }
+
+        embed
+    }
+
+    pub async fn score_chart(&self) -> Option<(CreateEmbed, CreateAttachment)> {
+        let mut points: Vec<(i64, i64)> = self
+            .score_history
+            .iter()
+            .map(|(time, score)| (time.unix_timestamp(), *score))
+            .collect();
+        points.push((Timestamp::now().unix_timestamp(), self.total_score()));
+
+        if points.len() < 2 {
+            return None;
+        }
+
+        let chart = match line_chart(&points) {
+            Ok(chart) => chart,
+            Err(e) => {
+                Logger::error(
+                    "member.score_chart",
+                    &format!(
+                        "cannot render score chart of member {}: {}",
+                        self.id.get(),
+                        e
+                    ),
+                )
+                .await;
+                return None;
+            }
+        };
+
+        let embed = CreateEmbed::new()
+            .title(loc!("member-chart-score-title"))
+            .description(loc!(
+                "member-chart-score-description",
+                "since" = points[0].0,
+                "min" = points.iter().map(|x| x.1).min().unwrap_or(0),
+                "max" = points.iter().map(|x| x.1).max().unwrap_or(0)
+            ))
+            .image("attachment://score.png")
+            .colour(Colour::BLUE);
+
+        Some((embed, CreateAttachment::bytes(chart, "score.png")))
+    }
+
+    pub async fn profile_embed(&self, ctx: &Context, view: &ProfileView) -> (CreateEmbed, usize) {
+        let pages = paginate_fields(
+            self.profile_sections(view.tab, view.show_secret).await,
+            PAGE_FIELDS,
+        );
+        let page = view.page.min(pages.len() - 1);
+
+        let mut embed = self
+            .profile_header(ctx)
+            .await
+            .footer(CreateEmbedFooter::new(loc!(
+                "member-profile-footer",
+                "tab" = view.tab.name(),
+                "page" = page + 1,
+                "pages" = pages.len()
+            )));
+
+        if pages[page].is_empty() {
+            embed = embed.description(loc!("member-profile-empty"));
+        }
+
+        for (name, value) in pages[page].iter() {
+            embed = embed.field(name, value, false);
+        }
+
+        (embed, pages.len())
+    }
+}
+
+impl ProfileView {
+    pub fn new(member: UserId, show_secret: bool) -> Self {
+        Self {
+            member,
+            tab: ProfileTab::Overview,
+            page: 0,
+            show_secret,
+        }
+    }
+
+    pub fn components(&self, pages: usize) -> Vec<CreateActionRow> {
+        Vec::from([
+            CreateActionRow::SelectMenu(CreateSelectMenu::new(
+                "member-profile:tab",
+                CreateSelectMenuKind::String {
+                    options: ProfileTab::all(self.show_secret)
+                        .into_iter()
+                        .map(|tab| {
+                            CreateSelectMenuOption::new(tab.name(), tab.id())
+                                .default_selection(tab == self.tab)
+                        })
+                        .collect(),
+                },
+            )),
+            CreateActionRow::Buttons(Vec::from([
+                CreateButton::new("member-profile:previous")
+                    .emoji('◀')
+                    .style(ButtonStyle::Secondary)
+                    .disabled(pages <= 1),
+                CreateButton::new("member-profile:next")
+                    .emoji('▶')
+                    .style(ButtonStyle::Secondary)
+                    .disabled(pages <= 1),
+            ])),
+        ])
+    }
+}
+
+async fn update_view(
+    ctx: &Context,
+    inter: ComponentInteraction,
+    change: impl FnOnce(&mut ProfileView, usize),
+) {
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    let Some(mut view) = mem_man
+        .get(inter.user.id)
+        .await
+        .unwrap()
+        .profile_view
+        .clone()
+    else {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("member-profile-no-view"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    };
+
+    let member = mem_man.get(view.member).await.unwrap().clone();
+    drop(mem_man);
+
+    let (_, pages) = member.profile_embed(ctx, &view).await;
+    change(&mut view, pages);
+
+    let (embed, pages) = member.profile_embed(ctx, &view).await;
+    view.page = view.page.min(pages - 1);
+
+    MEMBERSMANAGER
+        .write()
+        .await
+        .get_mut(inter.user.id)
+        .await
+        .unwrap()
+        .profile_view = Some(view.clone());
+
+    inter
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(view.components(pages)),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn profile_listener() {
+    #[listen_component("member-profile:tab")]
+    async fn tab(ctx: &Context, inter: ComponentInteraction) {
+        let ComponentInteractionDataKind::StringSelect { values } = &inter.data.kind else {
+            return;
+        };
+        let tab = ProfileTab::from_id(values.first().map(|x| x.as_str()).unwrap_or_default());
+
+        update_view(ctx, inter, |view, _| {
+            if view.show_secret || !matches!(tab, ProfileTab::Notes | ProfileTab::Warns) {
+                view.tab = tab;
+            }
+            view.page = 0;
+        })
+        .await;
+    }
+
+    #[listen_component("member-profile:previous")]
+    async fn previous(ctx: &Context, inter: ComponentInteraction) {
+        update_view(ctx, inter, |view, pages| {
+            view.page = (view.page + pages - 1) % pages;
+        })
+        .await;
+    }
+
+    #[listen_component("member-profile:next")]
+    async fn next(ctx: &Context, inter: ComponentInteraction) {
+        update_view(ctx, inter, |view, pages| {
+            view.page = (view.page + 1) % pages;
+        })
+        .await;
+    }
+}
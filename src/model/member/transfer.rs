@@ -0,0 +1,516 @@
+use crate::{
+    model::member::{ProjectMember, MEMBERSMANAGER},
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed},
+    model::{
+        guild::Member,
+        id::{ChannelId, MessageId, RoleId, UserId},
+        timestamp::Timestamp,
+    },
+};
+
+const DAY: i64 = 86400;
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct TransferPolicy {
+    #[serde(default)]
+    pub daily_amount: Option<i64>,
+    #[serde(default)]
+    pub daily_transfers: Option<usize>,
+    #[serde(default)]
+    pub min_account_days: Option<i64>,
+    #[serde(default)]
+    pub allowed_roles: Vec<RoleId>,
+    #[serde(default)]
+    pub approval_threshold: Option<i64>,
+    #[serde(default)]
+    pub approval_channel: Option<ChannelId>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum TransferState {
+    Pending,
+    Done,
+    Approved(UserId),
+    Denied(UserId),
+    Failed,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct ScoreTransfer {
+    pub from: UserId,
+    pub to: UserId,
+    pub amount: i64,
+    pub reason: String,
+    pub time: Timestamp,
+    #[serde(default)]
+    pub message: Option<MessageId>,
+    pub state: TransferState,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransferError {
+    NotFound,
+    SelfTransfer,
+    Bot,
+    NotEnoughScore(i64),
+    NotEligible,
+    DailyAmount(i64),
+    DailyTransfers(usize),
+    ApprovalUnavailable,
+}
+
+impl TransferError {
+    pub fn describe(&self) -> String {
+        match self {
+            Self::NotFound => loc!("give-score-member-not-found"),
+            Self::SelfTransfer => loc!("give-score-self-error"),
+            Self::Bot => loc!("give-score-bot-error"),
+            Self::NotEnoughScore(score) => loc!("give-score-not-enough-error", "score" = score),
+            Self::NotEligible => loc!("give-score-not-eligible-error"),
+            Self::DailyAmount(left) => loc!("give-score-daily-amount-error", "left" = left),
+            Self::DailyTransfers(num) => loc!("give-score-daily-transfers-error", "num" = num),
+            Self::ApprovalUnavailable => loc!("give-score-approval-unavailable-error"),
+        }
+    }
+}
+
+impl TransferPolicy {
+    pub fn is_eligible(&self, roles: &[RoleId], created: Timestamp, now: i64) -> bool {
+        if self.min_account_days.is_none() && self.allowed_roles.is_empty() {
+            return true;
+        }
+
+        self.min_account_days
+            .is_some_and(|days| now - created.unix_timestamp() >= days * DAY)
+            || self.allowed_roles.iter().any(|role| roles.contains(role))
+    }
+
+    pub fn check_limits(
+        &self,
+        history: &[ScoreTransfer],
+        from: UserId,
+        amount: i64,
+        now: i64,
+    ) -> Result<(), TransferError> {
+        let sent: Vec<&ScoreTransfer> = history
+            .iter()
+            .filter(|x| {
+                x.from == from
+                    && now - x.time.unix_timestamp() < DAY
+                    && !matches!(x.state, TransferState::Denied(_) | TransferState::Failed)
+            })
+            .collect();
+
+        if let Some(max) = self.daily_transfers {
+            if sent.len() >= max {
+                return Err(TransferError::DailyTransfers(max));
+            }
+        }
+
+        if let Some(max) = self.daily_amount {
+            let left = max - sent.iter().map(|x| x.amount).sum::<i64>();
+            if amount > left {
+                return Err(TransferError::DailyAmount(left.max(0)));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn needs_approval(&self, amount: i64) -> bool {
+        self.approval_channel.is_some() && self.approval_threshold.is_some_and(|x| amount >= x)
+    }
+
+    pub fn check(
+        &self,
+        sender: &ProjectMember,
+        dis_sender: &Member,
+        receiver: &Member,
+        amount: i64,
+    ) -> Result<(), TransferError> {
+        let now = Timestamp::now().unix_timestamp();
+
+        if sender.id == receiver.user.id {
+            return Err(TransferError::SelfTransfer);
+        }
+
+        if receiver.user.bot {
+            return Err(TransferError::Bot);
+        }
+
+        if !self.is_eligible(&dis_sender.roles, dis_sender.user.id.created_at(), now) {
+            return Err(TransferError::NotEligible);
+        }
+
+        if sender.total_score() < amount {
+            return Err(TransferError::NotEnoughScore(sender.total_score()));
+        }
+
+        self.check_limits(&sender.score_transfers, sender.id, amount, now)
+    }
+}
+
+impl ScoreTransfer {
+    pub fn new(from: UserId, to: UserId, amount: i64, reason: String) -> Self {
+        Self {
+            from,
+            to,
+            amount,
+            reason,
+            time: Timestamp::now(),
+            message: None,
+            state: TransferState::Pending,
+        }
+    }
+
+    pub fn line(&self, member: UserId) -> String {
+        let mut line = match self.from == member {
+            true => loc!(
+                "give-score-line-sent",
+                "amount" = self.amount,
+                "member" = self.to.get(),
+                "time" = self.time.unix_timestamp(),
+                "reason" = self.reason
+            ),
+            false => loc!(
+                "give-score-line-received",
+                "amount" = self.amount,
+                "member" = self.from.get(),
+                "time" = self.time.unix_timestamp(),
+                "reason" = self.reason
+            ),
+        };
+
+        match self.state {
+            TransferState::Pending => {
+                line = format!("{} *{}*", line, loc!("give-score-state-pending"))
+            }
+            TransferState::Denied(_) => {
+                line = format!("{} *{}*", line, loc!("give-score-state-denied"))
+            }
+            TransferState::Failed => {
+                line = format!("{} *{}*", line, loc!("give-score-state-failed"))
+            }
+            TransferState::Done | TransferState::Approved(_) => (),
+        }
+
+        line
+    }
+
+    fn to_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::new()
+            .title(loc!("give-score-approval-title"))
+            .color(match self.state {
+                TransferState::Pending => Colour::ORANGE,
+                TransferState::Done | TransferState::Approved(_) => Colour::DARK_GREEN,
+                TransferState::Denied(_) | TransferState::Failed => Colour::RED,
+            })
+            .description(loc!(
+                "give-score-approval-description",
+                "from" = self.from.get(),
+                "to" = self.to.get(),
+                "amount" = self.amount
+            ))
+            .field(
+                loc!("give-score-reason-name"),
+                truncate_text(self.reason.clone(), 1024),
+                false,
+            );
+
+        match self.state {
+            TransferState::Approved(user) => {
+                embed = embed.field(
+                    loc!("give-score-result-name"),
+                    loc!("give-score-approved-by", "user" = user.get()),
+                    false,
+                );
+            }
+            TransferState::Denied(user) => {
+                embed = embed.field(
+                    loc!("give-score-result-name"),
+                    loc!("give-score-denied-by", "user" = user.get()),
+                    false,
+                );
+            }
+            TransferState::Pending | TransferState::Done | TransferState::Failed => (),
+        }
+
+        embed
+    }
+
+    fn approval_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("score-transfer:approve")
+                .label(loc!("give-score-approve-button"))
+                .style(ButtonStyle::Success),
+            CreateButton::new("score-transfer:deny")
+                .label(loc!("give-score-deny-button"))
+                .style(ButtonStyle::Danger),
+        ]))])
+    }
+}
+
+async fn complete_transfer(transfer: &ScoreTransfer) {
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    if let Ok(receiver) = mem_man.get_mut(transfer.to).await {
+        receiver.deposit(transfer.amount);
+        receiver.score_transfers.push(transfer.clone());
+        receiver.update().await;
+        receiver.check_achievements().await;
+    }
+    drop(mem_man);
+
+    Logger::medium(
+        "transfer.complete_transfer",
+        &format!(
+            "member {} transferred {} score to member {}: {}",
+            transfer.from.get(),
+            transfer.amount,
+            transfer.to.get(),
+            transfer.reason
+        ),
+    )
+    .await;
+
+    if let Err(e) = transfer
+        .to
+        .direct_message(
+            get_http(),
+            CreateMessage::new().content(loc!(
+                "give-score-received-dm",
+                "from" = transfer.from.get(),
+                "amount" = transfer.amount,
+                "reason" = transfer.reason
+            )),
+        )
+        .await
+    {
+        Logger::debug(
+            "transfer.complete_transfer",
+            &format!(
+                "cannot notify member {} about score transfer: {}",
+                transfer.to.get(),
+                e
+            ),
+        )
+        .await;
+    }
+}
+
+pub async fn give_score(
+    dis_sender: &Member,
+    receiver: &Member,
+    amount: i64,
+    reason: String,
+    policy: &TransferPolicy,
+) -> Result<TransferState, TransferError> {
+    let (from, to) = (dis_sender.user.id, receiver.user.id);
+    let mut transfer = ScoreTransfer::new(from, to, amount, reason);
+
+    if !policy.needs_approval(amount) {
+        transfer.state = TransferState::Done;
+    }
+
+    let mut mem_man = MEMBERSMANAGER.write().await;
+    let sender = mem_man
+        .get_mut(from)
+        .await
+        .map_err(|_| TransferError::NotFound)?;
+    policy.check(sender, dis_sender, receiver, amount)?;
+    sender.withdraw(amount);
+    sender.score_transfers.push(transfer.clone());
+    sender.update().await;
+    drop(mem_man);
+
+    if transfer.state == TransferState::Done {
+        complete_transfer(&transfer).await;
+        return Ok(TransferState::Done);
+    }
+
+    let sent = match policy.approval_channel {
+        Some(channel) => channel
+            .send_message(
+                get_http(),
+                CreateMessage::new()
+                    .embed(transfer.to_embed())
+                    .components(ScoreTransfer::approval_buttons()),
+            )
+            .await
+            .map_err(|e| e.to_string()),
+        None => Err("approval channel is not configured".to_string()),
+    };
+
+    let mut mem_man = MEMBERSMANAGER.write().await;
+    if let Ok(sender) = mem_man.get_mut(from).await {
+        if let Some(last) =
+            sender.score_transfers.iter_mut().rev().find(|x| {
+                x.time == transfer.time && x.to == to && x.state == TransferState::Pending
+            })
+        {
+            match &sent {
+                Ok(message) => last.message = Some(message.id),
+                Err(_) => {
+                    last.state = TransferState::Failed;
+                    sender.deposit(amount);
+                }
+            }
+        }
+        sender.update().await;
+    }
+    drop(mem_man);
+
+    if let Err(e) = sent {
+        Logger::error(
+            "transfer.give_score",
+            &format!(
+                "cannot send score transfer of member {} to approval channel, refunded: {}",
+                from.get(),
+                e
+            ),
+        )
+        .await;
+        return Err(TransferError::ApprovalUnavailable);
+    }
+
+    Logger::medium(
+        "transfer.give_score",
+        &format!(
+            "member {} requested approval to transfer {} score to member {}",
+            from.get(),
+            amount,
+            to.get()
+        ),
+    )
+    .await;
+
+    Ok(TransferState::Pending)
+}
+
+async fn resolve_transfer(ctx: &Context, inter: ComponentInteraction, approve: bool) {
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    let Some(member_id) = mem_man
+        .get_all()
+        .iter()
+        .find(|member| {
+            member.score_transfers.iter().any(|x| {
+                x.from == member.id
+                    && x.message == Some(inter.message.id)
+                    && x.state == TransferState::Pending
+            })
+        })
+        .map(|member| member.id)
+    else {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("give-score-not-found"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    };
+
+    let sender = mem_man.get_mut(member_id).await.unwrap();
+    let transfer = sender
+        .score_transfers
+        .iter_mut()
+        .find(|x| x.message == Some(inter.message.id) && x.state == TransferState::Pending)
+        .unwrap();
+
+    transfer.state = match approve {
+        true => TransferState::Approved(inter.user.id),
+        false => TransferState::Denied(inter.user.id),
+    };
+    let transfer = transfer.clone();
+
+    if !approve {
+        sender.deposit(transfer.amount);
+    }
+    sender.update().await;
+    drop(mem_man);
+
+    Logger::high(
+        "transfer.resolve_transfer",
+        &format!(
+            "user {} {} score transfer of {} from member {} to member {}",
+            inter.user.id.get(),
+            match approve {
+                true => "approved",
+                false => "denied",
+            },
+            transfer.amount,
+            transfer.from.get(),
+            transfer.to.get()
+        ),
+    )
+    .await;
+
+    if approve {
+        complete_transfer(&transfer).await;
+    }
+
+    if let Err(e) = transfer
+        .from
+        .direct_message(
+            &ctx.http,
+            CreateMessage::new().content(match approve {
+                true => loc!(
+                    "give-score-approved-dm",
+                    "to" = transfer.to.get(),
+                    "amount" = transfer.amount
+                ),
+                false => loc!(
+                    "give-score-denied-dm",
+                    "to" = transfer.to.get(),
+                    "amount" = transfer.amount
+                ),
+            }),
+        )
+        .await
+    {
+        Logger::debug(
+            "transfer.resolve_transfer",
+            &format!(
+                "cannot send transfer result to member {}: {}",
+                transfer.from.get(),
+                e
+            ),
+        )
+        .await;
+    }
+
+    inter
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(transfer.to_embed())
+                    .components(Vec::new()),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn transfer_listener() {
+    #[listen_component("score-transfer:approve")]
+    async fn approve(ctx: &Context, inter: ComponentInteraction) {
+        resolve_transfer(ctx, inter, true).await;
+    }
+
+    #[listen_component("score-transfer:deny")]
+    async fn deny(ctx: &Context, inter: ComponentInteraction) {
+        resolve_transfer(ctx, inter, false).await;
+    }
+}
@@ -0,0 +1,203 @@
+use crate::{
+    model::member::{ProjectMember, TaskHistory},
+    prelude::*,
+};
+use serenity::model::timestamp::Timestamp;
+use std::collections::HashMap;
+
+const SCORE_HISTORY_LIMIT: usize = 365;
+
+pub fn attribute_score(score: i64, weights: &HashMap<String, usize>) -> HashMap<String, i64> {
+    let total: usize = weights.values().sum();
+
+    if score <= 0 || total == 0 {
+        return HashMap::new();
+    }
+
+    let mut projects: Vec<(&String, &usize)> = weights.iter().filter(|(_, w)| **w > 0).collect();
+    projects.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+
+    let mut scores: HashMap<String, i64> = projects
+        .iter()
+        .map(|(project, weight)| ((*project).clone(), score * **weight as i64 / total as i64))
+        .collect();
+
+    let rest = score - scores.values().sum::<i64>();
+    *scores.get_mut(projects[0].0).unwrap() += rest;
+
+    scores
+}
+
+fn push_score_point(history: &mut Vec<(Timestamp, i64)>, time: Timestamp, total: i64) {
+    match history.last_mut() {
+        Some(last) if last.0.unix_timestamp() / 86400 == time.unix_timestamp() / 86400 => {
+            *last = (time, total);
+        }
+        _ => history.push((time, total)),
+    }
+
+    if history.len() > SCORE_HISTORY_LIMIT {
+        let extra = history.len() - SCORE_HISTORY_LIMIT;
+        history.drain(..extra);
+    }
+}
+
+pub fn merge_score_history(
+    first: &[(Timestamp, i64)],
+    second: &[(Timestamp, i64)],
+) -> Vec<(Timestamp, i64)> {
+    let mut times: Vec<Timestamp> = first.iter().chain(second.iter()).map(|x| x.0).collect();
+    times.sort_by_key(|x| x.unix_timestamp());
+
+    let value_at = |history: &[(Timestamp, i64)], time: &Timestamp| {
+        history
+            .iter()
+            .take_while(|x| x.0.unix_timestamp() <= time.unix_timestamp())
+            .last()
+            .map(|x| x.1)
+            .unwrap_or(0)
+    };
+
+    let mut merged = Vec::new();
+    for time in times {
+        let total = value_at(first, &time) + value_at(second, &time);
+        push_score_point(&mut merged, time, total);
+    }
+
+    merged
+}
+
+impl ProjectMember {
+    pub fn total_score(&self) -> i64 {
+        self.score + self.project_scores.values().sum::<i64>()
+    }
+
+    pub fn project_score(&self, project: &String) -> i64 {
+        self.project_scores.get(project).cloned().unwrap_or(0)
+    }
+
+    pub fn credit(&mut self, amount: i64) {
+        if amount > 0 {
+            self.all_time_score += amount;
+        }
+        self.deposit(amount);
+    }
+
+    pub fn deposit(&mut self, amount: i64) {
+        self.score += amount;
+        self.record_score();
+    }
+
+    pub fn withdraw(&mut self, amount: i64) {
+        let mut rest = amount;
+
+        if self.score > 0 {
+            let taken = rest.min(self.score);
+            self.score -= taken;
+            rest -= taken;
+        }
+
+        let mut wallets: Vec<(&String, &mut i64)> = self
+            .project_scores
+            .iter_mut()
+            .filter(|(_, score)| **score > 0)
+            .collect();
+        wallets.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+        for (_, score) in wallets {
+            if rest <= 0 {
+                break;
+            }
+
+            let taken = rest.min(*score);
+            *score -= taken;
+            rest -= taken;
+        }
+
+        self.score -= rest;
+        self.record_score();
+    }
+
+    pub async fn change_project_score(&mut self, project: &String, score: i64) {
+        *self.project_scores.entry(project.clone()).or_default() += score;
+        if score > 0 {
+            self.all_time_score += score;
+        }
+        self.record_score();
+        self.update().await;
+        self.check_achievements().await;
+
+        Logger::low(
+            "member.change_project_score",
+            &format!(
+                "score of member {} in project \"{}\" changed by {}",
+                self.id.get(),
+                project,
+                score
+            ),
+        )
+        .await;
+    }
+
+    pub fn record_score(&mut self) {
+        let total = self.total_score();
+        push_score_point(&mut self.score_history, Timestamp::now(), total);
+    }
+
+    pub fn backfill_score_history(&mut self, task_score: impl Fn(u32, bool) -> i64) -> bool {
+        if !self.score_history.is_empty() {
+            return false;
+        }
+
+        let mut earned: Vec<(Timestamp, i64)> = Vec::new();
+        for (tasks, mentor) in [(&self.done_tasks, false), (&self.mentor_tasks, true)] {
+            for history in tasks.values() {
+                for task in history.iter() {
+                    if let TaskHistory::Current(map) = task {
+                        earned.extend(
+                            map.iter()
+                                .map(|(time, id)| (*time, task_score(*id, mentor))),
+                        );
+                    }
+                }
+            }
+        }
+        earned.sort_by_key(|x| x.0.unix_timestamp());
+
+        let mut total = 0;
+        for (time, score) in earned {
+            total += score;
+            push_score_point(&mut self.score_history, time, total);
+        }
+        self.record_score();
+
+        true
+    }
+
+    pub fn migrate_project_scores(&mut self) -> bool {
+        if self.scores_migrated {
+            return false;
+        }
+
+        let mut weights: HashMap<String, usize> = HashMap::new();
+        for tasks in [&self.done_tasks, &self.mentor_tasks] {
+            for (project, history) in tasks.iter() {
+                *weights.entry(project.clone()).or_default() += history
+                    .iter()
+                    .map(|task| match task {
+                        TaskHistory::Current(map) => map.len(),
+                        TaskHistory::OldFormat(_) => 1,
+                    })
+                    .sum::<usize>();
+            }
+        }
+
+        for (project, score) in attribute_score(self.score, &weights) {
+            *self.project_scores.entry(project).or_default() += score;
+            self.score -= score;
+        }
+
+        self.scores_migrated = true;
+        true
+    }
+}
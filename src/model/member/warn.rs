@@ -0,0 +1,556 @@
+use crate::{
+    model::{
+        member::{NotesHistory, ProjectMember, MEMBERSMANAGER},
+        task::TASKMANAGER,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{
+        ActionRowComponent, ButtonStyle, Colour, ComponentInteractionDataKind, CreateActionRow,
+        CreateButton, CreateEmbed, CreateInputText, CreateModal, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption, InputTextStyle,
+    },
+    model::{
+        id::{ChannelId, MessageId, RoleId, UserId},
+        timestamp::Timestamp,
+    },
+};
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct WarnPolicy {
+    #[serde(default)]
+    pub expire_days: Option<i64>,
+    #[serde(default)]
+    pub appeal_channel: Option<ChannelId>,
+    #[serde(default)]
+    pub thresholds: Vec<WarnThreshold>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WarnThreshold {
+    pub warns: usize,
+    pub actions: Vec<WarnAction>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum WarnAction {
+    Timeout { duration: i64 },
+    RemoveRoles { roles: Vec<RoleId> },
+    LeaveTasks,
+    NotifyStaff,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum AppealState {
+    Pending,
+    Approved(UserId),
+    Denied(UserId),
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct WarnAppeal {
+    pub warn: Timestamp,
+    pub text: String,
+    pub created: Timestamp,
+    pub message: Option<MessageId>,
+    pub state: AppealState,
+}
+
+impl WarnPolicy {
+    pub fn is_active(&self, warn: &NotesHistory) -> bool {
+        match (warn, self.expire_days) {
+            (NotesHistory::Current((_, time, _)), Some(days)) => {
+                time.unix_timestamp() + days * 86400 > Timestamp::now().unix_timestamp()
+            }
+            _ => true,
+        }
+    }
+}
+
+impl ProjectMember {
+    pub fn active_warns(&self, policy: &WarnPolicy) -> Vec<&NotesHistory> {
+        self.warns.iter().filter(|x| policy.is_active(x)).collect()
+    }
+
+    pub fn expired_warns(&self, policy: &WarnPolicy) -> Vec<&NotesHistory> {
+        self.warns.iter().filter(|x| !policy.is_active(x)).collect()
+    }
+
+    pub fn warn_line(&self, warn: &NotesHistory) -> String {
+        match warn {
+            NotesHistory::OldFormat(string) => string.clone(),
+            NotesHistory::Current((user, time, string)) => {
+                let mut line = format!("<@{}> <t:{}:D>: {}", user.get(), time.timestamp(), string);
+
+                if let Some(appeal) = self.warn_appeals.iter().rev().find(|x| &x.warn == time) {
+                    line = format!(
+                        "{} *{}*",
+                        line,
+                        match appeal.state {
+                            AppealState::Pending => loc!("warn-appeal-state-pending"),
+                            AppealState::Approved(_) => loc!("warn-appeal-state-approved"),
+                            AppealState::Denied(_) => loc!("warn-appeal-state-denied"),
+                        }
+                    );
+                }
+
+                line
+            }
+        }
+    }
+
+    fn warn_index(&self, time: &Timestamp) -> Option<usize> {
+        self.warns.iter().position(|warn| match warn {
+            NotesHistory::Current((_, warn_time, _)) => warn_time == time,
+            NotesHistory::OldFormat(_) => false,
+        })
+    }
+}
+
+pub async fn escalate_warns(ctx: &Context, member_id: UserId) {
+    let policy = CONFIG.read().await.warn_policy.clone();
+
+    let (active, tasks) = {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let Ok(member) = mem_man.get(member_id).await else {
+            return;
+        };
+
+        (
+            member.active_warns(&policy).len(),
+            member
+                .in_tasks
+                .values()
+                .flatten()
+                .cloned()
+                .collect::<Vec<u32>>(),
+        )
+    };
+
+    let Some(threshold) = policy.thresholds.iter().find(|x| x.warns == active) else {
+        return;
+    };
+
+    Logger::high(
+        "warn.escalate_warns",
+        &format!(
+            "member {} reached {} active warns, applying {:?}",
+            member_id.get(),
+            active,
+            threshold.actions
+        ),
+    )
+    .await;
+
+    for action in threshold.actions.iter() {
+        match action {
+            WarnAction::Timeout { duration } => {
+                let Ok(time) =
+                    Timestamp::from_unix_timestamp(Timestamp::now().unix_timestamp() + duration)
+                else {
+                    continue;
+                };
+
+                if let Ok(mut member) = fetch_member(&member_id).await {
+                    Logger::if_ok(
+                        "warn.escalate_warns",
+                        "cannot timeout member",
+                        member
+                            .disable_communication_until_datetime(&ctx.http, time)
+                            .await,
+                    )
+                    .await;
+                }
+            }
+            WarnAction::RemoveRoles { roles } => {
+                if let Ok(member) = fetch_member(&member_id).await {
+                    for role in roles.iter() {
+                        if member.roles.contains(role) {
+                            Logger::if_ok(
+                                "warn.escalate_warns",
+                                "cannot remove role from member",
+                                member.remove_role(&ctx.http, role).await,
+                            )
+                            .await;
+                        }
+                    }
+                }
+            }
+            WarnAction::LeaveTasks => {
+                for id in tasks.iter() {
+                    if let Some(task) = TASKMANAGER.write().await.get_mut(*id) {
+                        task.remove_member(ctx, member_id).await;
+                    }
+                }
+            }
+            WarnAction::NotifyStaff => {
+                Logger::notify(
+                    "warn.escalate_warns",
+                    &loc!(
+                        "warn-threshold-notify",
+                        "member" = member_id.get(),
+                        "warns" = active
+                    ),
+                )
+                .await;
+            }
+        }
+    }
+}
+
+fn appeal_embed(member: &ProjectMember, appeal: &WarnAppeal) -> CreateEmbed {
+    let warn = match member.warn_index(&appeal.warn) {
+        Some(index) => member.warn_line(&member.warns[index]),
+        None => loc!("warn-appeal-warn-removed"),
+    };
+
+    let mut embed = CreateEmbed::new()
+        .title(loc!("warn-appeal-embed-title"))
+        .color(match appeal.state {
+            AppealState::Pending => Colour::ORANGE,
+            AppealState::Approved(_) => Colour::DARK_GREEN,
+            AppealState::Denied(_) => Colour::RED,
+        })
+        .field(
+            loc!("warn-appeal-embed-member-name"),
+            format!("<@{}>", member.id.get()),
+            false,
+        )
+        .field(loc!("warn-appeal-embed-warn-name"), warn, false)
+        .field(
+            loc!("warn-appeal-embed-text-name"),
+            truncate_text(appeal.text.clone(), 1024),
+            false,
+        );
+
+    match appeal.state {
+        AppealState::Pending => (),
+        AppealState::Approved(user) => {
+            embed = embed.field(
+                loc!("warn-appeal-embed-result-name"),
+                loc!("warn-appeal-approved-by", "user" = user.get()),
+                false,
+            );
+        }
+        AppealState::Denied(user) => {
+            embed = embed.field(
+                loc!("warn-appeal-embed-result-name"),
+                loc!("warn-appeal-denied-by", "user" = user.get()),
+                false,
+            );
+        }
+    }
+
+    embed
+}
+
+pub fn appeal_select(member: &ProjectMember, policy: &WarnPolicy) -> Option<CreateActionRow> {
+    let mut options = Vec::new();
+
+    for warn in member.active_warns(policy) {
+        if let NotesHistory::Current((_, time, text)) = warn {
+            if member.warn_appeals.iter().any(|x| &x.warn == time) {
+                continue;
+            }
+
+            options.push(CreateSelectMenuOption::new(
+                truncate_text(text.clone(), 100),
+                time.unix_timestamp().to_string(),
+            ));
+        }
+    }
+
+    if options.is_empty() {
+        return None;
+    }
+
+    Some(CreateActionRow::SelectMenu(
+        CreateSelectMenu::new(
+            "warn-appeal:select",
+            CreateSelectMenuKind::String {
+                options: options.into_iter().take(25).collect(),
+            },
+        )
+        .placeholder(loc!("warn-appeal-select-placeholder")),
+    ))
+}
+
+async fn resolve_appeal(ctx: &Context, inter: ComponentInteraction, approve: bool) {
+    let mut mem_man = MEMBERSMANAGER.write().await;
+
+    let Some(member_id) = mem_man
+        .get_all()
+        .iter()
+        .find(|member| {
+            member
+                .warn_appeals
+                .iter()
+                .any(|x| x.message == Some(inter.message.id))
+        })
+        .map(|member| member.id)
+    else {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("warn-appeal-not-found"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    };
+
+    let member = mem_man.get_mut(member_id).await.unwrap();
+    let Some(appeal_index) = member
+        .warn_appeals
+        .iter()
+        .position(|x| x.message == Some(inter.message.id))
+    else {
+        return;
+    };
+
+    if member.warn_appeals[appeal_index].state != AppealState::Pending {
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Message(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("warn-appeal-already-resolved"))
+                        .ephemeral(true),
+                ),
+            )
+            .await
+            .unwrap();
+        return;
+    }
+
+    let warn = member.warn_appeals[appeal_index].warn;
+    let embed_before = appeal_embed(member, &member.warn_appeals[appeal_index]);
+
+    if approve {
+        if let Some(index) = member.warn_index(&warn) {
+            member.remove_warn(inter.user.id, index).await;
+        }
+        member.warn_appeals[appeal_index].state = AppealState::Approved(inter.user.id);
+    } else {
+        member.warn_appeals[appeal_index].state = AppealState::Denied(inter.user.id);
+    }
+    member.update().await;
+
+    Logger::high(
+        "warn.resolve_appeal",
+        &format!(
+            "user {} {} warn appeal of member {}",
+            inter.user.id.get(),
+            match approve {
+                true => "approved",
+                false => "denied",
+            },
+            member_id.get()
+        ),
+    )
+    .await;
+
+    let embed = appeal_embed(member, &member.warn_appeals[appeal_index]);
+    drop(mem_man);
+
+    if let Err(e) = member_id
+        .direct_message(
+            &ctx.http,
+            CreateMessage::new()
+                .content(match approve {
+                    true => loc!("warn-appeal-approved-dm"),
+                    false => loc!("warn-appeal-denied-dm"),
+                })
+                .embed(embed_before),
+        )
+        .await
+    {
+        Logger::debug(
+            "warn.resolve_appeal",
+            &format!(
+                "cannot send appeal result to member {}: {}",
+                member_id.get(),
+                e
+            ),
+        )
+        .await;
+    }
+
+    inter
+        .create_response(
+            &ctx.http,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .embed(embed)
+                    .components(Vec::new()),
+            ),
+        )
+        .await
+        .unwrap();
+}
+
+pub async fn warn_listener() {
+    #[listen_component("warn-appeal:select")]
+    async fn appeal_select_warn(ctx: &Context, inter: ComponentInteraction) {
+        let ComponentInteractionDataKind::StringSelect { ref values } = inter.data.kind else {
+            return;
+        };
+
+        let Some(time) = values
+            .first()
+            .and_then(|x| x.parse::<i64>().ok())
+            .and_then(|x| Timestamp::from_unix_timestamp(x).ok())
+        else {
+            return;
+        };
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let member = mem_man.get_mut(inter.user.id).await.unwrap();
+
+        member.changed_warn = member.warns.iter().find_map(|warn| match warn {
+            NotesHistory::Current((_, warn_time, _))
+                if warn_time.unix_timestamp() == time.unix_timestamp() =>
+            {
+                Some(*warn_time)
+            }
+            _ => None,
+        });
+        drop(mem_man);
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::Modal(
+                    CreateModal::new("warn-appeal:text", loc!("warn-appeal-modal-title"))
+                        .components(Vec::from([CreateActionRow::InputText(
+                            CreateInputText::new(
+                                InputTextStyle::Paragraph,
+                                loc!("warn-appeal-modal-input-label"),
+                                "warn-appeal:text:input",
+                            )
+                            .max_length(1000),
+                        )])),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_modal("warn-appeal:text")]
+    async fn appeal_submit(ctx: &Context, inter: ModalInteraction) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let mut text = String::new();
+        for row in inter.data.components.iter() {
+            for comp in row.components.iter() {
+                if let ActionRowComponent::InputText(input) = comp {
+                    if input.custom_id == "warn-appeal:text:input" {
+                        text = input.value.clone().unwrap_or_default();
+                    }
+                }
+            }
+        }
+
+        let Some(channel) = CONFIG.read().await.warn_policy.appeal_channel else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("warn-appeal-no-channel")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let member = mem_man.get_mut(inter.user.id).await.unwrap();
+
+        let Some(warn) = member.changed_warn.take() else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("warn-appeal-not-found")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut appeal = WarnAppeal {
+            warn,
+            text,
+            created: Timestamp::now(),
+            message: None,
+            state: AppealState::Pending,
+        };
+
+        match channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new()
+                    .embed(appeal_embed(member, &appeal))
+                    .components(Vec::from([CreateActionRow::Buttons(Vec::from([
+                        CreateButton::new("warn-appeal:approve")
+                            .label(loc!("warn-appeal-approve-button"))
+                            .style(ButtonStyle::Success),
+                        CreateButton::new("warn-appeal:deny")
+                            .label(loc!("warn-appeal-deny-button"))
+                            .style(ButtonStyle::Danger),
+                    ]))])),
+            )
+            .await
+        {
+            Ok(message) => {
+                appeal.message = Some(message.id);
+                member.warn_appeals.push(appeal);
+                member.update().await;
+
+                Logger::medium(
+                    "warn.appeal_submit",
+                    &format!("member {} appealed a warn", member.id.get()),
+                )
+                .await;
+
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("warn-appeal-sent")),
+                    )
+                    .await
+                    .unwrap();
+            }
+            Err(e) => {
+                inter
+                    .edit_response(
+                        &ctx.http,
+                        EditInteractionResponse::new().content(loc!("warn-appeal-send-error")),
+                    )
+                    .await
+                    .unwrap();
+                Logger::error(
+                    "warn.appeal_submit",
+                    &format!("cannot send warn appeal to staff channel: {}", e),
+                )
+                .await;
+            }
+        }
+    }
+
+    #[listen_component("warn-appeal:approve")]
+    async fn appeal_approve(ctx: &Context, inter: ComponentInteraction) {
+        resolve_appeal(ctx, inter, true).await;
+    }
+
+    #[listen_component("warn-appeal:deny")]
+    async fn appeal_deny(ctx: &Context, inter: ComponentInteraction) {
+        resolve_appeal(ctx, inter, false).await;
+    }
+}
@@ -1,5 +1,8 @@
+pub mod achievement;
+pub mod leaderboard;
 pub mod member;
 pub mod project;
 pub mod role;
+pub mod season;
 pub mod tag;
 pub mod task;
@@ -0,0 +1,359 @@
+use crate::{
+    model::{
+        member::MEMBERSMANAGER,
+        project::{Project, ProjectManager, PROJECTMANAGER},
+        tag::TAGSMANAGER,
+        task::TASKMANAGER,
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serenity::{
+    all::{
+        ActionRowComponent, ComponentInteractionDataKind, CreateActionRow, CreateSelectMenu,
+        CreateSelectMenuKind, CreateSelectMenuOption,
+    },
+    model::id::{RoleId, UserId},
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ProjectPermission {
+    TaskChange,
+    TaskClose,
+    ProjectConfig,
+    TagChange,
+    MemberChange,
+}
+
+impl ProjectPermission {
+    pub const ALL: [Self; 5] = [
+        Self::TaskChange,
+        Self::TaskClose,
+        Self::ProjectConfig,
+        Self::TagChange,
+        Self::MemberChange,
+    ];
+
+    pub fn id(&self) -> &'static str {
+        match self {
+            Self::TaskChange => "task-change",
+            Self::TaskClose => "task-close",
+            Self::ProjectConfig => "project-config",
+            Self::TagChange => "tag-change",
+            Self::MemberChange => "member-change",
+        }
+    }
+
+    pub fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|x| x.id() == id)
+    }
+
+    pub fn name(&self) -> String {
+        loc!(&format!("project-permission-{}", self.id()))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ManagerRole {
+    pub role: RoleId,
+    pub permissions: Vec<ProjectPermission>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Caller {
+    pub id: UserId,
+    pub roles: Vec<RoleId>,
+    pub admin: bool,
+}
+
+impl Caller {
+    pub fn new(id: UserId, member: Option<&Member>) -> Self {
+        Self {
+            id,
+            roles: member.map(|x| x.roles.clone()).unwrap_or_default(),
+            admin: member
+                .and_then(|x| x.permissions)
+                .is_some_and(|x| x.administrator() || x.manage_guild()),
+        }
+    }
+
+    pub fn from_command(inter: &CommandInteraction) -> Self {
+        Self::new(inter.user.id, inter.member.as_deref())
+    }
+
+    pub fn is_manager_only(&self, proj_man: &ProjectManager) -> bool {
+        !self.admin
+            && proj_man
+                .projects()
+                .into_iter()
+                .filter_map(|name| proj_man.get(name))
+                .any(|project| {
+                    project
+                        .managers
+                        .iter()
+                        .any(|x| self.roles.contains(&x.role))
+                })
+    }
+}
+
+impl Project {
+    pub fn allows(
+        &self,
+        caller: &Caller,
+        permission: ProjectPermission,
+        manager_only: bool,
+    ) -> bool {
+        caller.admin
+            || self
+                .managers
+                .iter()
+                .any(|x| caller.roles.contains(&x.role) && x.permissions.contains(&permission))
+            || (self.managers.is_empty() && !manager_only)
+    }
+
+    pub async fn set_manager(&mut self, role: RoleId, permissions: Vec<ProjectPermission>) {
+        self.managers.retain(|x| x.role != role);
+
+        if !permissions.is_empty() {
+            self.managers.push(ManagerRole {
+                role,
+                permissions: permissions.clone(),
+            });
+        }
+        self.update().await;
+
+        Logger::high(
+            "project.set_manager",
+            &format!(
+                "manager role {} of project \"{}\" set to {:?}",
+                role.get(),
+                self.name(),
+                permissions
+            ),
+        )
+        .await;
+    }
+
+    pub fn manager_select(&self, role: RoleId) -> CreateActionRow {
+        let current = self
+            .managers
+            .iter()
+            .find(|x| x.role == role)
+            .map(|x| x.permissions.clone())
+            .unwrap_or_default();
+
+        CreateActionRow::SelectMenu(
+            CreateSelectMenu::new(
+                "project-managers:permissions",
+                CreateSelectMenuKind::String {
+                    options: ProjectPermission::ALL
+                        .into_iter()
+                        .map(|x| {
+                            CreateSelectMenuOption::new(x.name(), x.id())
+                                .default_selection(current.contains(&x))
+                        })
+                        .collect(),
+                },
+            )
+            .placeholder(loc!("project-managers-placeholder"))
+            .min_values(0)
+            .max_values(ProjectPermission::ALL.len() as u8),
+        )
+    }
+}
+
+impl ProjectManager {
+    pub fn allows(&self, caller: &Caller, project: &String, permission: ProjectPermission) -> bool {
+        let manager_only = caller.is_manager_only(self);
+
+        match self.get(project) {
+            Some(project) => project.allows(caller, permission, manager_only),
+            None => !manager_only,
+        }
+    }
+}
+
+pub async fn check_project(
+    caller: &Caller,
+    project: &String,
+    permission: ProjectPermission,
+) -> bool {
+    PROJECTMANAGER
+        .read()
+        .await
+        .allows(caller, project, permission)
+}
+
+pub async fn check_forum(caller: &Caller, forum: ChannelId, permission: ProjectPermission) -> bool {
+    let proj_man = PROJECTMANAGER.read().await;
+
+    match proj_man.get_from_forum(&forum) {
+        Some(project) => project.allows(caller, permission, caller.is_manager_only(&proj_man)),
+        None => !caller.is_manager_only(&proj_man),
+    }
+}
+
+pub async fn check_task(caller: &Caller, thread: ChannelId, permission: ProjectPermission) -> bool {
+    let task = TASKMANAGER.read().await.get_thread(thread).map(|task| {
+        (
+            task.project.clone(),
+            task.members.get().contains(&caller.id) || task.mentor_id.get() == &Some(caller.id),
+        )
+    });
+
+    match task {
+        Some((_, true)) if permission == ProjectPermission::TaskClose => true,
+        Some((project, _)) => check_project(caller, &project, permission).await,
+        None => check_global(caller).await,
+    }
+}
+
+pub async fn check_global(caller: &Caller) -> bool {
+    !caller.is_manager_only(&*PROJECTMANAGER.read().await)
+}
+
+pub async fn check_component(custom_id: &str, caller: &Caller, values: &[String]) -> bool {
+    if custom_id == "member-changer" || custom_id == "member-changer:tasks" {
+        return true;
+    }
+
+    if custom_id.starts_with("member-changer:tasks:") {
+        for value in values {
+            let project = value.split(":::").next().unwrap_or_default().to_string();
+            if !check_project(caller, &project, ProjectPermission::MemberChange).await {
+                return false;
+            }
+        }
+        return true;
+    }
+
+    if custom_id.starts_with("member-changer") || custom_id.starts_with("project-managers") {
+        return check_global(caller).await;
+    }
+
+    if ![
+        "task-changer",
+        "task-close:",
+        "project-changer",
+        "tag-changer",
+    ]
+    .iter()
+    .any(|x| custom_id.starts_with(x))
+    {
+        return true;
+    }
+
+    let author = MEMBERSMANAGER
+        .read()
+        .await
+        .find(caller.id)
+        .map(|x| (x.changed_task, x.changed_project.clone(), x.changed_tag));
+    let Some((changed_task, changed_project, changed_tag)) = author else {
+        return check_global(caller).await;
+    };
+
+    if custom_id.starts_with("task-changer") || custom_id.starts_with("task-close:") {
+        let permission = match custom_id.starts_with("task-close:") {
+            true => ProjectPermission::TaskClose,
+            false => ProjectPermission::TaskChange,
+        };
+
+        let thread = match changed_task {
+            Some(id) => TASKMANAGER.read().await.get(id).map(|task| task.thread_id),
+            None => None,
+        };
+
+        return match thread {
+            Some(thread) => check_task(caller, thread, permission).await,
+            None => check_global(caller).await,
+        };
+    }
+
+    if custom_id.starts_with("project-changer") {
+        return match changed_project {
+            Some(project) => {
+                check_project(caller, &project, ProjectPermission::ProjectConfig).await
+            }
+            None => check_global(caller).await,
+        };
+    }
+
+    if custom_id.starts_with("tag-changer") {
+        let forum = match changed_tag {
+            Some(id) => TAGSMANAGER.read().await.get(&id).map(|tag| tag.forum_id),
+            None => None,
+        };
+
+        return match forum {
+            Some(forum) => check_forum(caller, forum, ProjectPermission::TagChange).await,
+            None => check_global(caller).await,
+        };
+    }
+
+    true
+}
+
+pub fn component_values(inter: &ComponentInteraction) -> Vec<String> {
+    match &inter.data.kind {
+        ComponentInteractionDataKind::StringSelect { values } => values.clone(),
+        _ => Vec::new(),
+    }
+}
+
+pub fn modal_values(inter: &ModalInteraction) -> Vec<String> {
+    let mut values = Vec::new();
+
+    for row in inter.data.components.iter() {
+        for component in row.components.iter() {
+            if let ActionRowComponent::InputText(input) = component {
+                if input.custom_id.ends_with("project-input") {
+                    values.extend(input.value.clone());
+                }
+            }
+        }
+    }
+
+    values
+}
+
+pub async fn managers_listener() {
+    #[listen_component("project-managers:permissions")]
+    async fn permissions_response(ctx: &Context, inter: ComponentInteraction) {
+        let (project, role) = match member::MEMBERSMANAGER.read().await.find(inter.user.id) {
+            Some(member) => (member.changed_project.clone(), member.changed_role),
+            None => (None, None),
+        };
+
+        let (Some(project), Some(role)) = (project, role) else {
+            return;
+        };
+
+        let permissions: Vec<ProjectPermission> = component_values(&inter)
+            .iter()
+            .filter_map(|x| ProjectPermission::from_id(x))
+            .collect();
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let content = match proj_man.get_mut(&project) {
+            Some(project) => {
+                project.set_manager(role, permissions).await;
+                loc!("command-done-response")
+            }
+            None => loc!("project-not-found"),
+        };
+        drop(proj_man);
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .content(content)
+                        .components(Vec::new()),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -5,6 +5,7 @@ use serenity::{
         ActionRowComponent, ComponentInteractionDataKind, CreateActionRow, CreateInputText,
         CreateModal,
     },
+    model::{id::RoleId, timestamp::Timestamp},
 };
 
 pub async fn project_listen() {
@@ -101,6 +102,394 @@ pub async fn project_listen() {
         }
     }
 
+    #[listen_component("project-changer:required-sections")]
+    async fn required_sections_response(ctx: &Context, inter: ComponentInteraction) {
+        let mut men_man = member::MEMBERSMANAGER.write().await;
+        let proj_man = project::PROJECTMANAGER.read().await;
+        let member = men_man.get(inter.user.id).await.unwrap();
+
+        if let Some(project) = proj_man.get(&member.changed_project.clone().unwrap()) {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Modal(
+                        CreateModal::new(
+                            "project-changer:required-sections",
+                            loc!("project-changer-required-sections-modal-title"),
+                        )
+                        .components(Vec::from([
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Paragraph,
+                                    loc!("project-changer-required-sections-input-label"),
+                                    "project-changer:required-sections:input",
+                                )
+                                .value(project.required_sections.join("\n"))
+                                .required(false),
+                            ),
+                        ])),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[listen_modal("project-changer:required-sections")]
+    async fn required_sections_submit(ctx: &Context, inter: ModalInteraction) {
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let mut mem_man = member::MEMBERSMANAGER.write().await;
+
+        if let Some(project) = proj_man.get_mut(
+            &mem_man
+                .get(inter.user.id)
+                .await
+                .unwrap()
+                .changed_project
+                .clone()
+                .unwrap(),
+        ) {
+            for row in inter.data.components.iter() {
+                for comp in row.components.iter() {
+                    if let ActionRowComponent::InputText(text) = comp {
+                        project
+                            .set_required_sections(
+                                text.value
+                                    .clone()
+                                    .unwrap_or_default()
+                                    .lines()
+                                    .map(|x| x.trim().to_string())
+                                    .filter(|x| !x.is_empty())
+                                    .collect(),
+                            )
+                            .await;
+                    }
+                }
+            }
+
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new(),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[listen_component("project-changer:inactivity")]
+    async fn inactivity_response(ctx: &Context, inter: ComponentInteraction) {
+        let mut men_man = member::MEMBERSMANAGER.write().await;
+        let proj_man = project::PROJECTMANAGER.read().await;
+        let member = men_man.get(inter.user.id).await.unwrap();
+
+        if let Some(project) = proj_man.get(&member.changed_project.clone().unwrap()) {
+            let policy = project.inactivity.clone();
+
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Modal(
+                        CreateModal::new(
+                            "project-changer:inactivity",
+                            loc!("project-changer-inactivity-modal-title"),
+                        )
+                        .components(Vec::from([
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-inactivity-days-label"),
+                                    "project-changer:inactivity:days",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .map(|x| x.days.to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-inactivity-grace-label"),
+                                    "project-changer:inactivity:grace",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .map(|x| x.grace_days.to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-inactivity-role-label"),
+                                    "project-changer:inactivity:role",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .and_then(|x| x.downgrade_role)
+                                        .map(|x| x.get().to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                        ])),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[listen_modal("project-changer:inactivity")]
+    async fn inactivity_submit(ctx: &Context, inter: ModalInteraction) {
+        let mut days = String::new();
+        let mut grace = String::new();
+        let mut role = String::new();
+
+        for row in inter.data.components.iter() {
+            for comp in row.components.iter() {
+                if let ActionRowComponent::InputText(text) = comp {
+                    let value = text.value.clone().unwrap_or_default().trim().to_string();
+                    match text.custom_id.as_str() {
+                        "project-changer:inactivity:days" => days = value,
+                        "project-changer:inactivity:grace" => grace = value,
+                        "project-changer:inactivity:role" => role = value,
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        let policy = match days.is_empty() {
+            true => Ok(None),
+            false => match (
+                days.parse::<i64>(),
+                match grace.is_empty() {
+                    true => Ok(0),
+                    false => grace.parse::<i64>(),
+                },
+                match role.is_empty() {
+                    true => Ok(None),
+                    false => role.parse::<u64>().map(|x| Some(RoleId::new(x))),
+                },
+            ) {
+                (Ok(days), Ok(grace_days), Ok(downgrade_role)) if days > 0 && grace_days >= 0 => {
+                    Ok(Some(project::InactivityPolicy {
+                        days,
+                        grace_days,
+                        downgrade_role,
+                        enabled: Timestamp::now(),
+                    }))
+                }
+                _ => Err(()),
+            },
+        };
+
+        let Ok(policy) = policy else {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("project-changer-inactivity-parse-error"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let mut mem_man = member::MEMBERSMANAGER.write().await;
+
+        if let Some(project) = proj_man.get_mut(
+            &mem_man
+                .get(inter.user.id)
+                .await
+                .unwrap()
+                .changed_project
+                .clone()
+                .unwrap(),
+        ) {
+            project.set_inactivity(policy).await;
+
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new(),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[listen_component("project-changer:digest")]
+    async fn digest_response(ctx: &Context, inter: ComponentInteraction) {
+        let mut men_man = member::MEMBERSMANAGER.write().await;
+        let proj_man = project::PROJECTMANAGER.read().await;
+        let member = men_man.get(inter.user.id).await.unwrap();
+
+        if let Some(project) = proj_man.get(&member.changed_project.clone().unwrap()) {
+            let policy = project.digest.clone();
+
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Modal(
+                        CreateModal::new(
+                            "project-changer:digest",
+                            loc!("project-changer-digest-modal-title"),
+                        )
+                        .components(Vec::from([
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-digest-period-label"),
+                                    "project-changer:digest:period",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .map(|x| x.period_days.to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-digest-stale-label"),
+                                    "project-changer:digest:stale",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .map(|x| x.stale_days.to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                            CreateActionRow::InputText(
+                                CreateInputText::new(
+                                    serenity::all::InputTextStyle::Short,
+                                    loc!("project-changer-digest-channel-label"),
+                                    "project-changer:digest:channel",
+                                )
+                                .value(
+                                    policy
+                                        .as_ref()
+                                        .and_then(|x| x.channel)
+                                        .map(|x| x.get().to_string())
+                                        .unwrap_or_default(),
+                                )
+                                .required(false),
+                            ),
+                        ])),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
+    #[listen_modal("project-changer:digest")]
+    async fn digest_submit(ctx: &Context, inter: ModalInteraction) {
+        let mut period = String::new();
+        let mut stale = String::new();
+        let mut channel = String::new();
+
+        for row in inter.data.components.iter() {
+            for comp in row.components.iter() {
+                if let ActionRowComponent::InputText(text) = comp {
+                    let value = text.value.clone().unwrap_or_default().trim().to_string();
+                    match text.custom_id.as_str() {
+                        "project-changer:digest:period" => period = value,
+                        "project-changer:digest:stale" => stale = value,
+                        "project-changer:digest:channel" => channel = value,
+                        _ => (),
+                    }
+                }
+            }
+        }
+
+        let policy = match period.is_empty() {
+            true => Ok(None),
+            false => match (
+                period.parse::<i64>(),
+                match stale.is_empty() {
+                    true => Ok(14),
+                    false => stale.parse::<i64>(),
+                },
+                match channel.is_empty() {
+                    true => Ok(None),
+                    false => channel.parse::<u64>().map(|x| Some(ChannelId::new(x))),
+                },
+            ) {
+                (Ok(period_days), Ok(stale_days), Ok(channel))
+                    if period_days > 0 && stale_days > 0 =>
+                {
+                    Ok(Some(project::DigestPolicy {
+                        period_days,
+                        stale_days,
+                        channel,
+                    }))
+                }
+                _ => Err(()),
+            },
+        };
+
+        let Ok(policy) = policy else {
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::Message(
+                        CreateInteractionResponseMessage::new()
+                            .content(loc!("project-changer-digest-parse-error"))
+                            .ephemeral(true),
+                    ),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut proj_man = project::PROJECTMANAGER.write().await;
+        let mut mem_man = member::MEMBERSMANAGER.write().await;
+
+        if let Some(project) = proj_man.get_mut(
+            &mem_man
+                .get(inter.user.id)
+                .await
+                .unwrap()
+                .changed_project
+                .clone()
+                .unwrap(),
+        ) {
+            project.set_digest(policy).await;
+
+            inter
+                .create_response(
+                    &ctx.http,
+                    CreateInteractionResponse::UpdateMessage(
+                        CreateInteractionResponseMessage::new(),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+    }
+
     #[listen_component("project-changer:tasks-forum")]
     async fn task_forum_response(ctx: &Context, inter: ComponentInteraction) {
         let mut proj_man = project::PROJECTMANAGER.write().await;
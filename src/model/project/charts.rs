@@ -0,0 +1,162 @@
+use super::project::StatView;
+use crate::{
+    model::{member::MEMBERSMANAGER, project::Project, task::TASKMANAGER},
+    prelude::*,
+};
+use serenity::{
+    all::{Colour, CreateAttachment, CreateEmbed},
+    model::{id::RoleId, timestamp::Timestamp},
+};
+
+pub const CHART_WEEKS: usize = 12;
+
+impl Project {
+    pub fn role_distribution(&self, view: &StatView) -> Vec<(RoleId, u64)> {
+        self.associated_roles
+            .iter()
+            .map(|role| {
+                (
+                    *role,
+                    view.members
+                        .iter()
+                        .filter(|member| !member.user.bot && member.roles.contains(role))
+                        .count() as u64,
+                )
+            })
+            .collect()
+    }
+
+    pub async fn chart_embeds(&self, view: &StatView) -> (Vec<CreateEmbed>, Vec<CreateAttachment>) {
+        let mut embeds = Vec::new();
+        let mut files = Vec::new();
+
+        let closed: Vec<i64> = TASKMANAGER
+            .read()
+            .await
+            .get_by_project(&self.name)
+            .iter()
+            .filter(|task| task.finished)
+            .filter_map(|task| task.end_date.get().map(|x| x.unix_timestamp()))
+            .collect();
+        let weeks = bucket_by_period(
+            &closed,
+            Timestamp::now().unix_timestamp(),
+            7 * 86400,
+            CHART_WEEKS,
+        );
+
+        match bar_chart(&weeks) {
+            Ok(chart) => {
+                embeds.push(
+                    CreateEmbed::new()
+                        .title(loc!("project-chart-closed-title", "weeks" = CHART_WEEKS))
+                        .description(loc!(
+                            "project-chart-closed-description",
+                            "total" = weeks.iter().sum::<i64>(),
+                            "max" = weeks.iter().max().cloned().unwrap_or(0)
+                        ))
+                        .image("attachment://closed_tasks.png")
+                        .colour(Colour::MAGENTA),
+                );
+                files.push(CreateAttachment::bytes(chart, "closed_tasks.png"));
+            }
+            Err(e) => {
+                Logger::error(
+                    "project.chart_embeds",
+                    &format!(
+                        "cannot render tasks chart of project \"{}\": {}",
+                        self.name, e
+                    ),
+                )
+                .await;
+            }
+        }
+
+        let roles = self.role_distribution(view);
+        let total: u64 = roles.iter().map(|x| x.1).sum();
+
+        if total == 0 {
+            return (embeds, files);
+        }
+
+        match pie_chart(&roles.iter().map(|x| x.1).collect::<Vec<u64>>()) {
+            Ok(chart) => {
+                let legend: Vec<String> = roles
+                    .iter()
+                    .enumerate()
+                    .map(|(index, (role, count))| {
+                        loc!(
+                            "project-chart-roles-line",
+                            "mark" = palette_mark(index),
+                            "role" = role.get(),
+                            "count" = count,
+                            "percent" = count * 100 / total
+                        )
+                    })
+                    .collect();
+
+                embeds.push(
+                    CreateEmbed::new()
+                        .title(loc!("project-chart-roles-title"))
+                        .description(truncate_text(legend.join("\n"), 4000))
+                        .image("attachment://roles.png")
+                        .colour(Colour::MAGENTA),
+                );
+                files.push(CreateAttachment::bytes(chart, "roles.png"));
+            }
+            Err(e) => {
+                Logger::error(
+                    "project.chart_embeds",
+                    &format!(
+                        "cannot render roles chart of project \"{}\": {}",
+                        self.name, e
+                    ),
+                )
+                .await;
+            }
+        }
+
+        (embeds, files)
+    }
+
+    pub async fn stats_embed(&self) -> CreateEmbed {
+        let (total, open) = {
+            let task_man = TASKMANAGER.read().await;
+            let tasks = task_man.get_by_project(&self.name);
+            (
+                tasks.len(),
+                tasks.iter().filter(|task| !task.finished).count(),
+            )
+        };
+
+        let score: i64 = MEMBERSMANAGER
+            .read()
+            .await
+            .get_all()
+            .iter()
+            .map(|member| member.project_score(&self.name))
+            .sum();
+
+        CreateEmbed::new()
+            .title(loc!("project-stats-title", "project" = self.name))
+            .colour(Colour::MAGENTA)
+            .field(loc!("project-stats-tasks-name"), total.to_string(), true)
+            .field(loc!("project-stats-open-name"), open.to_string(), true)
+            .field(
+                loc!("project-stats-finished-name"),
+                (total - open).to_string(),
+                true,
+            )
+            .field(loc!("project-stats-score-name"), score.to_string(), true)
+    }
+}
+
+pub async fn project_charts(
+    ctx: &Context,
+    project: &Project,
+) -> (Vec<CreateEmbed>, Vec<CreateAttachment>) {
+    match StatView::new(ctx).await {
+        Some(view) => project.chart_embeds(&view).await,
+        None => (Vec::new(), Vec::new()),
+    }
+}
@@ -0,0 +1,363 @@
+use super::project::StatView;
+use crate::{
+    model::{
+        project::{Project, PROJECTMANAGER},
+        task::{Task, TASKMANAGER},
+    },
+    prelude::*,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use serenity::{
+    all::{Colour, CreateEmbed},
+    model::{id::UserId, timestamp::Timestamp},
+};
+use std::{cmp::Reverse, collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+
+pub static DIGESTSTATE: Lazy<Arc<RwLock<DigestState>>> =
+    Lazy::new(|| Arc::new(RwLock::new(DigestState::new())));
+
+const DIGEST_TOP_LIMIT: usize = 5;
+const DIGEST_STALE_LIMIT: usize = 10;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct DigestPolicy {
+    pub period_days: i64,
+    pub stale_days: i64,
+    pub channel: Option<ChannelId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct DigestSnapshot {
+    since: Timestamp,
+    members: Vec<UserId>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct DigestState {
+    projects: HashMap<u32, DigestSnapshot>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub struct DigestStats {
+    pub opened: usize,
+    pub closed: usize,
+    pub average_close: Option<i64>,
+    pub stale: Vec<(ChannelId, i64)>,
+    pub top: Vec<(UserId, i64)>,
+}
+
+impl DigestStats {
+    pub fn collect(tasks: &[&Task], since: i64, now: i64, stale_days: i64) -> Self {
+        let mut stats = Self::default();
+        let mut close_times = Vec::new();
+        let mut results: HashMap<UserId, i64> = HashMap::new();
+
+        for task in tasks.iter() {
+            let start = task.start_date.map(|x| x.unix_timestamp());
+            let end = task.end_date.get().map(|x| x.unix_timestamp());
+
+            if start.is_some_and(|x| x >= since && x < now) {
+                stats.opened += 1;
+            }
+
+            match (task.finished, start, end) {
+                (true, _, Some(end)) if end >= since && end < now => {
+                    stats.closed += 1;
+                    if let Some(start) = start {
+                        close_times.push(end - start);
+                    }
+                    for (member, result) in task.close_results.iter() {
+                        *results.entry(*member).or_default() += result;
+                    }
+                }
+                (false, Some(start), _) if now - start >= stale_days * 86400 => {
+                    stats.stale.push((task.thread_id, (now - start) / 86400));
+                }
+                _ => (),
+            }
+        }
+
+        if !close_times.is_empty() {
+            stats.average_close = Some(close_times.iter().sum::<i64>() / close_times.len() as i64);
+        }
+        stats.stale.sort_by_key(|x| Reverse(x.1));
+
+        stats.top = results
+            .into_iter()
+            .filter(|(_, score)| *score > 0)
+            .collect();
+        stats.top.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        stats.top.truncate(DIGEST_TOP_LIMIT);
+
+        stats
+    }
+}
+
+fn format_duration(seconds: i64) -> String {
+    loc!(
+        "project-digest-duration",
+        "days" = seconds / 86400,
+        "hours" = seconds % 86400 / 3600
+    )
+}
+
+impl DigestState {
+    fn new() -> Self {
+        let content = read_file(&DATA_PATH.join("project_digests.json"));
+
+        match content.as_str() {
+            "" => Self::default(),
+            _ => match serde_json::from_str(&content) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprint!("project digests deserialize error: {}", e);
+                    Self::default()
+                }
+            },
+        }
+    }
+
+    async fn write_data(&self) {
+        write_file(
+            &DATA_PATH.join("project_digests.json"),
+            match serde_json::to_string(&self) {
+                Ok(c) => c,
+                Err(e) => {
+                    Logger::error("project_digests.serialize", e.to_string().as_str()).await;
+                    return;
+                }
+            },
+        );
+    }
+
+    pub async fn replace_user(&mut self, from: UserId) {
+        for snapshot in self.projects.values_mut() {
+            snapshot.members.retain(|id| id != &from);
+        }
+
+        self.write_data().await;
+    }
+
+    pub async fn send_if_due(&mut self, ctx: &Context) {
+        let now = Timestamp::now().unix_timestamp();
+
+        let due: Vec<(Project, DigestPolicy)> = {
+            let proj_man = PROJECTMANAGER.read().await;
+
+            proj_man
+                .projects()
+                .into_iter()
+                .filter_map(|name| proj_man.get(name))
+                .filter(|project| !project.is_archived())
+                .filter_map(|project| Some((project.clone(), project.digest.clone()?)))
+                .filter(|(project, policy)| match self.projects.get(&project.id) {
+                    Some(snapshot) => {
+                        now - snapshot.since.unix_timestamp() >= policy.period_days * 86400
+                    }
+                    None => true,
+                })
+                .collect()
+        };
+
+        if due.is_empty() {
+            return;
+        }
+
+        let Some(view) = StatView::new(ctx).await else {
+            return;
+        };
+
+        for (project, policy) in due {
+            let snapshot = project.digest_snapshot(&view);
+
+            let sent = match self.projects.get(&project.id) {
+                Some(previous) => {
+                    project
+                        .send_digest(ctx, &view, &policy, previous, &snapshot)
+                        .await
+                }
+                None => true,
+            };
+
+            if sent {
+                self.projects.insert(project.id, snapshot);
+            }
+        }
+
+        self.write_data().await;
+    }
+}
+
+impl Project {
+    fn digest_snapshot(&self, view: &StatView) -> DigestSnapshot {
+        DigestSnapshot {
+            since: Timestamp::now(),
+            members: view
+                .members
+                .iter()
+                .filter(|member| {
+                    !member.user.bot
+                        && member
+                            .roles
+                            .iter()
+                            .any(|role| self.associated_roles.contains(role))
+                })
+                .map(|member| member.user.id)
+                .collect(),
+        }
+    }
+
+    async fn send_digest(
+        &self,
+        ctx: &Context,
+        view: &StatView,
+        policy: &DigestPolicy,
+        previous: &DigestSnapshot,
+        current: &DigestSnapshot,
+    ) -> bool {
+        let Some(channel) = policy.channel.or(self.stat_channel) else {
+            Logger::debug(
+                "project.send_digest",
+                &format!("project \"{}\" has no channel for digest", self.name),
+            )
+            .await;
+            return false;
+        };
+
+        let since = previous.since.unix_timestamp();
+        let now = current.since.unix_timestamp();
+
+        let stats = {
+            let task_man = TASKMANAGER.read().await;
+            DigestStats::collect(
+                &task_man.get_by_project(&self.name),
+                since,
+                now,
+                policy.stale_days,
+            )
+        };
+
+        let no_data = loc!("project-digest-no-data");
+
+        let top: Vec<String> = stats
+            .top
+            .iter()
+            .map(|(id, gain)| {
+                loc!(
+                    "project-digest-top-line",
+                    "member" = id.get(),
+                    "score" = gain
+                )
+            })
+            .collect();
+
+        let new_members: Vec<String> = current
+            .members
+            .iter()
+            .filter(|id| !previous.members.contains(id))
+            .map(|id| format!("<@{}>", id.get()))
+            .collect();
+
+        let mut stale: Vec<String> = stats
+            .stale
+            .iter()
+            .take(DIGEST_STALE_LIMIT)
+            .map(|(thread, days)| {
+                loc!(
+                    "project-digest-stale-line",
+                    "thread" = thread.get(),
+                    "days" = days
+                )
+            })
+            .collect();
+        if stats.stale.len() > DIGEST_STALE_LIMIT {
+            stale.push(loc!(
+                "project-digest-stale-more",
+                "count" = stats.stale.len() - DIGEST_STALE_LIMIT
+            ));
+        }
+
+        let embed = CreateEmbed::new()
+            .title(loc!("project-digest-title", "project" = self.name))
+            .description(loc!(
+                "project-digest-period",
+                "since" = since,
+                "until" = now
+            ))
+            .colour(Colour::MAGENTA)
+            .field(
+                loc!("project-digest-opened-name"),
+                stats.opened.to_string(),
+                true,
+            )
+            .field(
+                loc!("project-digest-closed-name"),
+                stats.closed.to_string(),
+                true,
+            )
+            .field(
+                loc!("project-digest-average-close-name"),
+                stats
+                    .average_close
+                    .map(format_duration)
+                    .unwrap_or(no_data.clone()),
+                true,
+            )
+            .field(
+                loc!("project-digest-top-name"),
+                match top.is_empty() {
+                    true => no_data.clone(),
+                    false => box_list(top),
+                },
+                false,
+            )
+            .field(
+                loc!(
+                    "project-digest-new-members-name",
+                    "count" = new_members.len()
+                ),
+                match new_members.is_empty() {
+                    true => no_data.clone(),
+                    false => truncate_text(new_members.join(", "), 1024),
+                },
+                false,
+            )
+            .field(
+                loc!(
+                    "project-digest-stale-name",
+                    "days" = policy.stale_days,
+                    "count" = stats.stale.len()
+                ),
+                match stale.is_empty() {
+                    true => no_data,
+                    false => truncate_text(box_list(stale), 1024),
+                },
+                false,
+            );
+
+        let (charts, files) = self.chart_embeds(view).await;
+        let mut embeds = Vec::from([embed]);
+        embeds.extend(charts);
+
+        match channel
+            .send_message(
+                &ctx.http,
+                CreateMessage::new().embeds(embeds).add_files(files),
+            )
+            .await
+        {
+            Ok(_) => true,
+            Err(e) => {
+                Logger::error(
+                    "project.send_digest",
+                    &format!("cannot send digest of project \"{}\": {}", self.name, e),
+                )
+                .await;
+                false
+            }
+        }
+    }
+}
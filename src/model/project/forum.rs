@@ -0,0 +1,118 @@
+use crate::{model::project::Project, prelude::*};
+use serde::{Deserialize, Serialize};
+use serenity::model::id::{ChannelId, ForumTagId, RoleId};
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TaskForum {
+    pub channel: ChannelId,
+    #[serde(default)]
+    pub waiter_role: Option<RoleId>,
+    #[serde(default)]
+    pub default_tags: Vec<ForumTagId>,
+}
+
+impl TaskForum {
+    pub fn new(channel: ChannelId) -> Self {
+        Self {
+            channel,
+            waiter_role: None,
+            default_tags: Vec::new(),
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        let mut text = format!("<#{}>", self.channel.get());
+
+        if let Some(role) = self.waiter_role {
+            text = format!("{} — <@&{}>", text, role.get());
+        }
+
+        if !self.default_tags.is_empty() {
+            text = format!(
+                "{} ({})",
+                text,
+                loc!(
+                    "project-forum-default-tags",
+                    "count" = self.default_tags.len()
+                )
+            );
+        }
+
+        text
+    }
+}
+
+impl Project {
+    pub fn forum_ids(&self) -> Vec<ChannelId> {
+        let mut forums = Vec::from([self.tasks_forum]);
+
+        for forum in self.forums.iter() {
+            if !forums.contains(&forum.channel) {
+                forums.push(forum.channel);
+            }
+        }
+
+        forums
+    }
+
+    pub fn has_forum(&self, channel: &ChannelId) -> bool {
+        &self.tasks_forum == channel || self.forums.iter().any(|x| &x.channel == channel)
+    }
+
+    pub fn forum(&self, channel: ChannelId) -> TaskForum {
+        let mut forum = self
+            .forums
+            .iter()
+            .find(|x| x.channel == channel)
+            .cloned()
+            .unwrap_or(TaskForum::new(channel));
+
+        if forum.waiter_role.is_none() {
+            forum.waiter_role = self.waiter_role;
+        }
+
+        forum
+    }
+
+    pub async fn set_forum(&mut self, forum: TaskForum) {
+        let channel = forum.channel;
+
+        match self.forums.iter_mut().find(|x| x.channel == channel) {
+            Some(old) => *old = forum,
+            None => self.forums.push(forum),
+        }
+        self.update().await;
+
+        Logger::high(
+            "project.set_forum",
+            &format!(
+                "task forum {} of project \"{}\" configured",
+                channel.get(),
+                self.name()
+            ),
+        )
+        .await;
+    }
+
+    pub async fn remove_forum(&mut self, channel: ChannelId) -> bool {
+        let len = self.forums.len();
+        self.forums.retain(|x| x.channel != channel);
+
+        if len == self.forums.len() {
+            return false;
+        }
+        self.update().await;
+
+        Logger::high(
+            "project.remove_forum",
+            &format!(
+                "task forum {} removed from project \"{}\"",
+                channel.get(),
+                self.name()
+            ),
+        )
+        .await;
+
+        true
+    }
+}
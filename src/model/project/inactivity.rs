@@ -0,0 +1,390 @@
+use crate::{
+    model::{
+        member::MEMBERSMANAGER,
+        project::{Project, PROJECTMANAGER},
+    },
+    prelude::*,
+};
+use serde::{Deserialize, Serialize};
+use serde_json;
+use serenity::model::{
+    id::{RoleId, UserId},
+    timestamp::Timestamp,
+};
+use tokio::time::{sleep, Duration};
+
+const REPORT_PERIOD: i64 = 7 * 86400;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InactivityPolicy {
+    pub days: i64,
+    pub grace_days: i64,
+    pub downgrade_role: Option<RoleId>,
+    #[serde(default = "Timestamp::now")]
+    pub enabled: Timestamp,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum InactivityDecision {
+    Keep,
+    ClearWarning,
+    Warn,
+    Remove,
+}
+
+impl InactivityPolicy {
+    pub fn decide(
+        &self,
+        now: i64,
+        last_activity: Option<i64>,
+        joined: Option<i64>,
+        warned: Option<i64>,
+    ) -> InactivityDecision {
+        let last = [last_activity, joined]
+            .into_iter()
+            .flatten()
+            .fold(self.enabled.unix_timestamp(), i64::max);
+
+        if now - last < self.days * 86400 {
+            return match warned {
+                Some(_) => InactivityDecision::ClearWarning,
+                None => InactivityDecision::Keep,
+            };
+        }
+
+        match warned {
+            None => InactivityDecision::Warn,
+            Some(warned) if now - warned >= self.grace_days * 86400 => InactivityDecision::Remove,
+            Some(_) => InactivityDecision::Keep,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub enum InactivityAction {
+    Warned,
+    Removed(Vec<RoleId>),
+    Downgraded(Vec<RoleId>, RoleId),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InactivityEvent {
+    pub project: String,
+    pub member: UserId,
+    pub time: Timestamp,
+    pub action: InactivityAction,
+}
+
+impl InactivityEvent {
+    fn describe(&self) -> String {
+        match &self.action {
+            InactivityAction::Warned => loc!(
+                "inactivity-report-warned",
+                "member" = self.member.get(),
+                "project" = self.project
+            ),
+            InactivityAction::Removed(roles) => loc!(
+                "inactivity-report-removed",
+                "member" = self.member.get(),
+                "project" = self.project,
+                "roles" = roles_mention(roles)
+            ),
+            InactivityAction::Downgraded(roles, role) => loc!(
+                "inactivity-report-downgraded",
+                "member" = self.member.get(),
+                "project" = self.project,
+                "roles" = roles_mention(roles),
+                "role" = role.get()
+            ),
+        }
+    }
+}
+
+fn roles_mention(roles: &[RoleId]) -> String {
+    roles
+        .iter()
+        .map(|role| format!("<@&{}>", role.get()))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+struct InactivityReport {
+    since: Option<Timestamp>,
+    events: Vec<InactivityEvent>,
+}
+
+impl InactivityReport {
+    fn new() -> Self {
+        let content = read_file(&DATA_PATH.join("inactivity_report.json"));
+
+        match content.as_str() {
+            "" => Self::default(),
+            _ => match serde_json::from_str(&content) {
+                Ok(c) => c,
+                Err(e) => {
+                    eprint!("inactivity report deserialize error: {}", e);
+                    Self::default()
+                }
+            },
+        }
+    }
+
+    async fn write_data(&self) {
+        write_file(
+            &DATA_PATH.join("inactivity_report.json"),
+            match serde_json::to_string(&self) {
+                Ok(c) => c,
+                Err(e) => {
+                    Logger::error("inactivity_report.serialize", e.to_string().as_str()).await;
+                    return;
+                }
+            },
+        );
+    }
+
+    async fn push(&mut self, event: InactivityEvent) {
+        Logger::medium(
+            "inactivity.check",
+            &format!(
+                "inactivity action {:?} for member {} in project \"{}\"",
+                event.action,
+                event.member.get(),
+                event.project
+            ),
+        )
+        .await;
+
+        self.events.push(event);
+        self.write_data().await;
+    }
+
+    async fn send_if_due(&mut self) {
+        let now = Timestamp::now().unix_timestamp();
+        let since = match self.since {
+            Some(since) => since,
+            None => {
+                self.since = Some(Timestamp::now());
+                self.write_data().await;
+                return;
+            }
+        };
+
+        if now - since.unix_timestamp() < REPORT_PERIOD {
+            return;
+        }
+
+        Logger::notify(
+            "inactivity.report",
+            &truncate_text(
+                format!(
+                    "{}\n{}",
+                    loc!(
+                        "inactivity-report-title",
+                        "since" = since.unix_timestamp(),
+                        "num" = self.events.len()
+                    ),
+                    match self.events.is_empty() {
+                        true => loc!("inactivity-report-empty"),
+                        false => box_list(self.events.iter().map(|x| x.describe()).collect()),
+                    }
+                ),
+                1800,
+            ),
+        )
+        .await;
+
+        self.since = Some(Timestamp::now());
+        self.events = Vec::new();
+        self.write_data().await;
+    }
+}
+
+pub async fn start_inactivity_check(ctx: Context) {
+    tokio::spawn(async move {
+        let timer = CONFIG.read().await.project_stat_update_duration;
+        let mut report = InactivityReport::new();
+
+        loop {
+            let projects: Vec<(Project, InactivityPolicy)> = {
+                let proj_man = PROJECTMANAGER.read().await;
+
+                proj_man
+                    .projects()
+                    .into_iter()
+                    .filter_map(|name| proj_man.get(name))
+                    .filter(|project| !project.is_archived())
+                    .filter_map(|project| Some((project.clone(), project.inactivity.clone()?)))
+                    .collect()
+            };
+
+            if !projects.is_empty() {
+                match fetch_members(&ctx.http).await {
+                    Ok(members) => {
+                        for (project, policy) in projects.iter() {
+                            check_project(&ctx, &members, project, policy, &mut report).await;
+                        }
+                    }
+                    Err(e) => {
+                        Logger::error(
+                            "inactivity.check",
+                            &format!("cannot fetch guild members: {}", e),
+                        )
+                        .await;
+                    }
+                }
+            }
+
+            report.send_if_due().await;
+            sleep(Duration::from_secs(timer)).await;
+        }
+    });
+}
+
+async fn check_project(
+    ctx: &Context,
+    members: &[Member],
+    project: &Project,
+    policy: &InactivityPolicy,
+    report: &mut InactivityReport,
+) {
+    let now = Timestamp::now().unix_timestamp();
+
+    for dis_member in members.iter() {
+        let roles: Vec<RoleId> = project
+            .associated_roles
+            .iter()
+            .filter(|role| dis_member.roles.contains(role) && Some(**role) != policy.downgrade_role)
+            .cloned()
+            .collect();
+
+        if roles.is_empty() {
+            continue;
+        }
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        let Ok(member) = mem_man.get_mut(dis_member.user.id).await else {
+            continue;
+        };
+
+        let decision = policy.decide(
+            now,
+            member
+                .last_activity
+                .get(&project.name)
+                .map(|x| x.unix_timestamp()),
+            dis_member.joined_at.map(|x| x.unix_timestamp()),
+            member
+                .inactivity_warnings
+                .get(&project.name)
+                .map(|x| x.unix_timestamp()),
+        );
+
+        match decision {
+            InactivityDecision::Keep => (),
+            InactivityDecision::ClearWarning => {
+                member.inactivity_warnings.remove(&project.name);
+                member.update().await;
+            }
+            InactivityDecision::Warn => {
+                member
+                    .inactivity_warnings
+                    .insert(project.name.clone(), Timestamp::now());
+                member.update().await;
+                drop(mem_man);
+
+                warn_member(ctx, project, policy, dis_member.user.id).await;
+
+                report
+                    .push(InactivityEvent {
+                        project: project.name.clone(),
+                        member: dis_member.user.id,
+                        time: Timestamp::now(),
+                        action: InactivityAction::Warned,
+                    })
+                    .await;
+            }
+            InactivityDecision::Remove => {
+                member.inactivity_warnings.remove(&project.name);
+                member.update().await;
+                drop(mem_man);
+
+                for role in roles.iter() {
+                    Logger::if_ok(
+                        "inactivity.check_project",
+                        "cannot remove project role from inactive member",
+                        dis_member.remove_role(&ctx.http, role).await,
+                    )
+                    .await;
+                }
+
+                let action = match policy.downgrade_role {
+                    Some(downgrade) => {
+                        if !dis_member.roles.contains(&downgrade) {
+                            Logger::if_ok(
+                                "inactivity.check_project",
+                                "cannot add downgrade role to inactive member",
+                                dis_member.add_role(&ctx.http, downgrade).await,
+                            )
+                            .await;
+                        }
+
+                        InactivityAction::Downgraded(roles, downgrade)
+                    }
+                    None => InactivityAction::Removed(roles),
+                };
+
+                report
+                    .push(InactivityEvent {
+                        project: project.name.clone(),
+                        member: dis_member.user.id,
+                        time: Timestamp::now(),
+                        action,
+                    })
+                    .await;
+            }
+        }
+    }
+}
+
+async fn warn_member(ctx: &Context, project: &Project, policy: &InactivityPolicy, member: UserId) {
+    let text = loc!(
+        "inactivity-warning",
+        "member" = member.get(),
+        "project" = project.name,
+        "days" = policy.days,
+        "grace" = policy.grace_days
+    );
+
+    if member
+        .direct_message(&ctx.http, CreateMessage::new().content(text.clone()))
+        .await
+        .is_ok()
+    {
+        return;
+    }
+
+    match project.stat_channel {
+        Some(channel) => {
+            Logger::if_ok(
+                "inactivity.warn_member",
+                "cannot send inactivity warning to stat channel",
+                channel
+                    .send_message(&ctx.http, CreateMessage::new().content(text))
+                    .await,
+            )
+            .await;
+        }
+        None => {
+            Logger::debug(
+                "inactivity.warn_member",
+                &format!(
+                    "cannot warn member {} about inactivity in project \"{}\"",
+                    member.get(),
+                    project.name
+                ),
+            )
+            .await;
+        }
+    }
+}
@@ -0,0 +1,295 @@
+use crate::{
+    model::{
+        achievement::ACHIEVEMENTMANAGER,
+        leaderboard::LEADERBOARDMANAGER,
+        member::{ProjectMember, MEMBERSMANAGER},
+        project::{Project, PROJECTMANAGER},
+        tag::TAGSMANAGER,
+        task::{Task, TASKMANAGER},
+    },
+    prelude::*,
+    shop::SHOPMANAGER,
+};
+use serde::{Deserialize, Serialize};
+use serenity::model::{
+    channel::{PermissionOverwrite, PermissionOverwriteType},
+    id::{ChannelId, RoleId, UserId},
+    permissions::Permissions,
+    timestamp::Timestamp,
+};
+use std::collections::HashMap;
+use std::fs;
+
+const FROZEN_PERMISSIONS: Permissions = Permissions::SEND_MESSAGES
+    .union(Permissions::SEND_MESSAGES_IN_THREADS)
+    .union(Permissions::CREATE_PUBLIC_THREADS)
+    .union(Permissions::CREATE_PRIVATE_THREADS)
+    .union(Permissions::ADD_REACTIONS);
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ProjectArchive {
+    pub time: Timestamp,
+    pub by: UserId,
+    pub overwrites: Vec<(ChannelId, Option<PermissionOverwrite>)>,
+}
+
+pub fn rename_key<T>(map: &mut HashMap<String, T>, from: &str, into: &str) -> bool {
+    match map.remove(from) {
+        Some(value) => {
+            map.insert(into.to_string(), value);
+            true
+        }
+        None => false,
+    }
+}
+
+impl Project {
+    pub fn is_archived(&self) -> bool {
+        self.archive.is_some()
+    }
+
+    pub async fn archive(&mut self, ctx: &Context, by: UserId) -> Result<(), String> {
+        if self.is_archived() {
+            return Err(format!("project \"{}\" is already archived", self.name));
+        }
+
+        let mut overwrites = Vec::new();
+
+        for channel in self.forum_ids() {
+            match freeze_forum(ctx, channel).await {
+                Ok(overwrite) => overwrites.push((channel, overwrite)),
+                Err(e) => {
+                    if let Err(rollback) = restore_overwrites(ctx, &overwrites).await {
+                        Logger::error(
+                            "project.archive",
+                            &format!(
+                                "cannot roll back partial archive of project \"{}\", recorded as archived: {}",
+                                self.name, rollback
+                            ),
+                        )
+                        .await;
+
+                        self.archive = Some(ProjectArchive {
+                            time: Timestamp::now(),
+                            by,
+                            overwrites,
+                        });
+                        self.update().await;
+                    }
+                    return Err(e);
+                }
+            }
+        }
+
+        self.archive = Some(ProjectArchive {
+            time: Timestamp::now(),
+            by,
+            overwrites,
+        });
+        self.update().await;
+
+        Logger::high(
+            "project.archive",
+            &format!("project \"{}\" archived by {}", self.name, by.get()),
+        )
+        .await;
+
+        Ok(())
+    }
+
+    pub async fn restore(&mut self, ctx: &Context, by: UserId) -> Result<(), String> {
+        let Some(archive) = self.archive.clone() else {
+            return Err(format!("project \"{}\" is not archived", self.name));
+        };
+
+        restore_overwrites(ctx, &archive.overwrites).await?;
+
+        self.archive = None;
+        self.update().await;
+
+        Logger::high(
+            "project.restore",
+            &format!("project \"{}\" restored by {}", self.name, by.get()),
+        )
+        .await;
+
+        Ok(())
+    }
+}
+
+fn everyone() -> PermissionOverwriteType {
+    PermissionOverwriteType::Role(RoleId::new(get_guild().get()))
+}
+
+async fn freeze_forum(
+    ctx: &Context,
+    channel: ChannelId,
+) -> Result<Option<PermissionOverwrite>, String> {
+    let forum = fetch_channel(ctx, channel)?;
+    let everyone = everyone();
+
+    let overwrite = forum
+        .permission_overwrites
+        .iter()
+        .find(|x| x.kind == everyone)
+        .cloned();
+
+    let (allow, deny) = match &overwrite {
+        Some(x) => (x.allow, x.deny),
+        None => (Permissions::empty(), Permissions::empty()),
+    };
+
+    forum
+        .create_permission(
+            &ctx.http,
+            PermissionOverwrite {
+                allow: allow.difference(FROZEN_PERMISSIONS),
+                deny: deny.union(FROZEN_PERMISSIONS),
+                kind: everyone,
+            },
+        )
+        .await
+        .map_err(|e| e.to_string())?;
+
+    Ok(overwrite)
+}
+
+async fn restore_overwrites(
+    ctx: &Context,
+    overwrites: &[(ChannelId, Option<PermissionOverwrite>)],
+) -> Result<(), String> {
+    for (channel, overwrite) in overwrites.iter() {
+        let forum = fetch_channel(ctx, *channel)?;
+
+        match overwrite {
+            Some(overwrite) => forum.create_permission(&ctx.http, overwrite.clone()).await,
+            None => forum.delete_permission(&ctx.http, everyone()).await,
+        }
+        .map_err(|e| e.to_string())?;
+    }
+
+    Ok(())
+}
+
+impl Task {
+    pub async fn rename_project(&mut self, from: &str, into: &str) -> bool {
+        if self.project != from {
+            return false;
+        }
+
+        self.project = into.to_string();
+        self.update().await;
+        true
+    }
+}
+
+impl ProjectMember {
+    pub fn rename_project(&mut self, from: &str, into: &str) -> bool {
+        let mut found = false;
+
+        found |= rename_key(&mut self.in_tasks, from, into);
+        found |= rename_key(&mut self.done_tasks, from, into);
+        found |= rename_key(&mut self.mentor_tasks, from, into);
+        found |= rename_key(&mut self.last_activity, from, into);
+        found |= rename_key(&mut self.inactivity_warnings, from, into);
+        found |= rename_key(&mut self.project_scores, from, into);
+
+        if let Some(application) = self.application.as_mut() {
+            if application.project.as_deref() == Some(from) {
+                application.project = Some(into.to_string());
+                found = true;
+            }
+        }
+
+        for project in [&mut self.changed_project, &mut self.onboarding_project] {
+            if project.as_deref() == Some(from) {
+                *project = Some(into.to_string());
+            }
+        }
+
+        found
+    }
+}
+
+pub async fn rename_project(from: &String, into: &String) -> Result<(usize, Vec<String>), String> {
+    PROJECTMANAGER.write().await.rename(from, into).await?;
+
+    let mut renamed = 0;
+
+    let mut task_man = TASKMANAGER.write().await;
+    for id in task_man
+        .get_by_project(from)
+        .iter()
+        .map(|x| x.id)
+        .collect::<Vec<u32>>()
+    {
+        if let Some(task) = task_man.get_mut(id) {
+            if task.rename_project(from, into).await {
+                renamed += 1;
+            }
+        }
+    }
+    drop(task_man);
+
+    let mut mem_man = MEMBERSMANAGER.write().await;
+    for id in mem_man
+        .get_all()
+        .iter()
+        .map(|x| x.id)
+        .collect::<Vec<UserId>>()
+    {
+        if let Ok(member) = mem_man.get_mut(id).await {
+            if member.rename_project(from, into) {
+                member.update().await;
+                renamed += 1;
+            }
+        }
+    }
+    drop(mem_man);
+
+    LEADERBOARDMANAGER
+        .write()
+        .await
+        .rename_project(from, into)
+        .await;
+    renamed += TAGSMANAGER.write().await.rename_project(from, into).await;
+
+    let mut configs = SHOPMANAGER.read().await.currency_references(from);
+    configs.extend(ACHIEVEMENTMANAGER.read().await.project_references(from));
+
+    let template = DATA_PATH.join(format!("stat_templates/{}.yml", from));
+    if fs::exists(&template).unwrap_or(false) {
+        Logger::if_ok(
+            "project.rename",
+            "cannot rename stat template",
+            fs::rename(
+                &template,
+                DATA_PATH.join(format!("stat_templates/{}.yml", into)),
+            ),
+        )
+        .await;
+    }
+
+    Logger::high(
+        "project.rename",
+        &format!(
+            "updated {} references while renaming project \"{}\" to \"{}\"",
+            renamed, from, into
+        ),
+    )
+    .await;
+
+    if !configs.is_empty() {
+        Logger::high(
+            "project.rename",
+            &format!(
+                "configs still reference old project name \"{}\": {}",
+                from,
+                configs.join(", ")
+            ),
+        )
+        .await;
+    }
+
+    Ok((renamed, configs))
+}
@@ -1,6 +1,20 @@
+mod access;
 mod changer_listen;
+mod charts;
+mod digest;
+mod forum;
+mod inactivity;
+mod lifecycle;
 mod project;
 mod project_changer;
+mod stat_template;
 
+pub use access::*;
 pub use changer_listen::*;
+pub use charts::{project_charts, CHART_WEEKS};
+pub use digest::{DigestPolicy, DigestState, DigestStats, DIGESTSTATE};
+pub use forum::*;
+pub use inactivity::{start_inactivity_check, InactivityDecision, InactivityPolicy};
+pub use lifecycle::*;
 pub use project::{Project, ProjectManager, PROJECTMANAGER};
+pub use stat_template::*;
@@ -1,24 +1,144 @@
-use crate::{model::member::MEMBERSMANAGER, prelude::*};
+use crate::{
+    model::{
+        member::MEMBERSMANAGER,
+        project::{
+            split_stat_fields, DigestPolicy, InactivityPolicy, ManagerRole, ProjectArchive,
+            StatGroup, StatSort, StatTemplate, TaskForum, DIGESTSTATE,
+        },
+        task::TASKMANAGER,
+    },
+    prelude::*,
+};
 use once_cell::sync::Lazy;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_json;
 use serenity::{
     all::Colour,
     builder::{CreateEmbed, EditMessage},
     model::{
-        guild::Member,
-        id::{ChannelId, MessageId, RoleId},
+        guild::{Member, Role},
+        id::{ChannelId, MessageId, RoleId, UserId},
     },
 };
 use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
 use std::{collections::HashMap, sync::Arc};
-use tokio::sync::RwLock;
+use tokio::sync::{Notify, RwLock};
 use tokio::time::{sleep, Duration};
 use walkdir::WalkDir;
 
 pub static PROJECTMANAGER: Lazy<Arc<RwLock<ProjectManager>>> =
     Lazy::new(|| Arc::new(RwLock::new(ProjectManager::new())));
 
+static STATUPDATE: Lazy<Notify> = Lazy::new(Notify::new);
+
+type StatPostKey = (String, RoleId, usize);
+
+static STATHASHES: Lazy<RwLock<HashMap<StatPostKey, u64>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+pub struct StatView {
+    pub members: Vec<Member>,
+    pub roles: HashMap<RoleId, Role>,
+}
+
+impl StatView {
+    pub async fn new(ctx: &Context) -> Option<Self> {
+        let cached = get_guild()
+            .to_guild_cached(&ctx.cache)
+            .filter(|guild| guild.members.len() as u64 >= guild.member_count)
+            .map(|guild| Self {
+                members: guild.members.values().cloned().collect(),
+                roles: guild.roles.clone(),
+            });
+
+        let view = match cached {
+            Some(view) => view,
+            None => {
+                match (
+                    fetch_members(&ctx.http).await,
+                    get_guild().roles(&ctx.http).await,
+                ) {
+                    (Ok(members), Ok(roles)) => Self { members, roles },
+                    (Err(e), _) | (_, Err(e)) => {
+                        Logger::error(
+                            "project.stat_view",
+                            &format!("cannot fetch guild members or roles: {}", e),
+                        )
+                        .await;
+                        return None;
+                    }
+                }
+            }
+        };
+
+        let missing: Vec<UserId> = {
+            let mem_man = MEMBERSMANAGER.read().await;
+            view.members
+                .iter()
+                .filter(|member| !member.user.bot && mem_man.find(member.user.id).is_none())
+                .map(|member| member.user.id)
+                .collect()
+        };
+
+        if !missing.is_empty() {
+            let mut mem_man = MEMBERSMANAGER.write().await;
+            for id in missing {
+                if let Err(e) = mem_man.get(id).await {
+                    Logger::error(
+                        "project.stat_view",
+                        &format!("cannot create member {}: {}", id.get(), e),
+                    )
+                    .await;
+                }
+            }
+        }
+
+        Some(view)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OldOrNewPosts {
+    Old(MessageId),
+    New(Vec<MessageId>),
+}
+
+fn deserialize_old_or_new_posts<'de, D>(
+    deserializer: D,
+) -> Result<HashMap<RoleId, Vec<MessageId>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Ok(HashMap::<RoleId, OldOrNewPosts>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(role, posts)| match posts {
+            OldOrNewPosts::Old(msg) => (role, Vec::from([msg])),
+            OldOrNewPosts::New(msgs) => (role, msgs),
+        })
+        .collect())
+}
+
+async fn delete_stat_post(ctx: &Context, channel: ChannelId, msg: MessageId) {
+    if let Ok(message) = channel.message(&ctx.http, msg).await {
+        Logger::if_ok(
+            "project.update_stat_post",
+            "cannot delete outdated stat post",
+            message.delete(&ctx.http).await,
+        )
+        .await;
+    }
+}
+
+fn stat_hash(embed: &CreateEmbed) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    serde_json::to_string(embed)
+        .unwrap_or_default()
+        .hash(&mut hasher);
+    hasher.finish()
+}
+
 #[derive(Debug)]
 pub struct ProjectManager {
     projects: HashMap<String, Project>,
@@ -37,6 +157,8 @@ impl ProjectManager {
                 .expect("error while creating folder data/databases/projects");
         }
 
+        let mut loaded = Vec::new();
+
         for entry in WalkDir::new(DATA_PATH.join("databases/projects")) {
             let entry = match entry {
                 Ok(s) => s,
@@ -71,6 +193,41 @@ impl ProjectManager {
                     }
                 };
 
+            loaded.push((entry.path().to_path_buf(), project));
+        }
+
+        for (_, project) in loaded.iter().filter(|(_, project)| project.id != 0) {
+            self.projects.insert(project.name.clone(), project.clone());
+        }
+
+        for (path, mut project) in loaded {
+            if project.id == 0 {
+                project.id = self.next_id();
+            }
+
+            if path.file_name().and_then(|x| x.to_str()) != Some(&project.id.to_string()) {
+                project.write().await;
+
+                Logger::if_ok(
+                    "proj_man.init",
+                    &format!(
+                        "cannot remove old data file of project \"{}\"",
+                        project.name
+                    ),
+                    fs::remove_file(&path),
+                )
+                .await;
+
+                Logger::debug(
+                    "proj_man.init",
+                    &format!(
+                        "migrated project \"{}\" to stable id {}",
+                        project.name, project.id
+                    ),
+                )
+                .await;
+            }
+
             self.projects.insert(project.name.clone(), project);
         }
 
@@ -87,13 +244,20 @@ impl ProjectManager {
     ) -> Result<(), String> {
         if !self.projects.contains_key(&name) {
             let project = Project {
+                id: self.next_id(),
                 name,
                 max_tasks_per_user,
                 tasks_forum,
                 waiter_role,
+                forums: Vec::new(),
                 stat_channel,
                 stat_posts: HashMap::new(),
                 associated_roles: Vec::new(),
+                required_sections: Vec::new(),
+                inactivity: None,
+                archive: None,
+                managers: Vec::new(),
+                digest: None,
             };
 
             Logger::high(
@@ -111,6 +275,36 @@ impl ProjectManager {
         Ok(())
     }
 
+    pub fn next_id(&self) -> u32 {
+        self.projects.values().map(|x| x.id).max().unwrap_or(0) + 1
+    }
+
+    pub fn get_by_id(&self, id: u32) -> Option<&Project> {
+        self.projects.values().find(|x| x.id == id)
+    }
+
+    pub async fn rename(&mut self, from: &String, into: &String) -> Result<(), String> {
+        if self.projects.contains_key(into) {
+            return Err(format!("project with name \"{}\" currently excist", into));
+        }
+
+        let Some(mut project) = self.projects.remove(from) else {
+            return Err(format!("project with name \"{}\" not found", from));
+        };
+
+        project.name = into.clone();
+        project.update().await;
+        self.projects.insert(into.clone(), project);
+
+        Logger::high(
+            "proj_man.rename",
+            &format!("renamed project \"{}\" to \"{}\"", from, into),
+        )
+        .await;
+
+        Ok(())
+    }
+
     pub async fn delete(&mut self, name: &String) -> Option<Project> {
         if let Some(proj) = self.projects.remove(name) {
             Logger::high(
@@ -127,21 +321,60 @@ impl ProjectManager {
 
     pub async fn start_update_stat(ctx: Context) {
         tokio::spawn(async move {
-            let timer = CONFIG.read().await.project_stat_update_duration;
+            let (timer, debounce) = {
+                let config = CONFIG.read().await;
+                (
+                    config.project_stat_update_duration,
+                    config.stat_update_debounce,
+                )
+            };
 
             loop {
-                let mut man = PROJECTMANAGER.write().await;
+                Self::update_stat_posts(&ctx).await;
+                DIGESTSTATE.write().await.send_if_due(&ctx).await;
 
-                for project in man.projects.values_mut() {
-                    project.update_stat_post(&ctx).await;
+                tokio::select! {
+                    _ = STATUPDATE.notified() => sleep(Duration::from_secs(debounce)).await,
+                    _ = sleep(Duration::from_secs(timer)) => (),
                 }
-
-                drop(man);
-                sleep(Duration::from_secs(timer)).await;
             }
         });
     }
 
+    pub fn request_stat_update() {
+        STATUPDATE.notify_one();
+    }
+
+    async fn update_stat_posts(ctx: &Context) {
+        let projects: Vec<Project> = PROJECTMANAGER
+            .read()
+            .await
+            .projects
+            .values()
+            .filter(|project| project.stat_channel.is_some())
+            .cloned()
+            .collect();
+
+        if projects.is_empty() {
+            return;
+        }
+
+        let Some(view) = StatView::new(ctx).await else {
+            return;
+        };
+
+        for project in projects {
+            let Some(posts) = project.update_stat_post(ctx, &view).await else {
+                continue;
+            };
+
+            if let Some(project) = PROJECTMANAGER.write().await.get_mut(&project.name) {
+                project.stat_posts = posts;
+                project.write().await;
+            }
+        }
+    }
+
     pub fn projects(&self) -> Vec<&String> {
         self.projects.keys().collect()
     }
@@ -155,33 +388,42 @@ impl ProjectManager {
     }
 
     pub fn get_from_forum(&self, forum: &ChannelId) -> Option<&Project> {
-        for project in self.projects.values() {
-            if &project.tasks_forum == forum {
-                return Some(project);
-            }
-        }
-        None
+        self.projects
+            .values()
+            .find(|project| project.has_forum(forum))
     }
 
     pub fn get_mut_from_forum(&mut self, forum: &ChannelId) -> Option<&mut Project> {
-        for project in self.projects.values_mut() {
-            if &project.tasks_forum == forum {
-                return Some(project);
-            }
-        }
-        None
+        self.projects
+            .values_mut()
+            .find(|project| project.has_forum(forum))
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Project {
+    #[serde(default)]
+    pub id: u32,
     pub name: String,
     pub max_tasks_per_user: u32,
     pub tasks_forum: ChannelId,
     pub waiter_role: Option<RoleId>,
-    pub stat_posts: HashMap<RoleId, MessageId>,
+    #[serde(default)]
+    pub forums: Vec<TaskForum>,
+    #[serde(deserialize_with = "deserialize_old_or_new_posts")]
+    pub stat_posts: HashMap<RoleId, Vec<MessageId>>,
     pub stat_channel: Option<ChannelId>,
     pub associated_roles: Vec<RoleId>,
+    #[serde(default)]
+    pub required_sections: Vec<String>,
+    #[serde(default)]
+    pub inactivity: Option<InactivityPolicy>,
+    #[serde(default)]
+    pub archive: Option<ProjectArchive>,
+    #[serde(default)]
+    pub managers: Vec<ManagerRole>,
+    #[serde(default)]
+    pub digest: Option<DigestPolicy>,
 }
 
 impl Project {
@@ -191,7 +433,7 @@ impl Project {
 
     async fn write(&self) {
         write_file(
-            &DATA_PATH.join(format!("databases/projects/{}", self.name())),
+            &DATA_PATH.join(format!("databases/projects/{}", self.id)),
             match serde_json::to_string(&self) {
                 Ok(content) => content,
                 Err(e) => {
@@ -279,6 +521,82 @@ impl Project {
         .await;
     }
 
+    pub async fn set_required_sections(&mut self, sections: Vec<String>) {
+        let old = self.required_sections.clone();
+        self.required_sections = sections;
+        self.update().await;
+
+        Logger::high(
+            "project.set_required_sections",
+            &format!(
+                "required sections of project \"{}\" changed from {:?} to {:?}",
+                self.name(),
+                old,
+                self.required_sections
+            ),
+        )
+        .await;
+    }
+
+    pub async fn set_inactivity(&mut self, mut inactivity: Option<InactivityPolicy>) {
+        let old = self.inactivity.clone();
+        if let (Some(old), Some(new)) = (&old, &mut inactivity) {
+            new.enabled = old.enabled;
+        }
+        self.inactivity = inactivity;
+        self.update().await;
+
+        Logger::high(
+            "project.set_inactivity",
+            &format!(
+                "inactivity policy of project \"{}\" changed from {:?} to {:?}",
+                self.name(),
+                old,
+                self.inactivity
+            ),
+        )
+        .await;
+    }
+
+    pub async fn set_digest(&mut self, digest: Option<DigestPolicy>) {
+        let old = self.digest.clone();
+        self.digest = digest;
+        self.update().await;
+
+        Logger::high(
+            "project.set_digest",
+            &format!(
+                "digest policy of project \"{}\" changed from {:?} to {:?}",
+                self.name(),
+                old,
+                self.digest
+            ),
+        )
+        .await;
+    }
+
+    pub fn missing_sections(&self, content: &str) -> Vec<String> {
+        let headers: Vec<String> = content
+            .lines()
+            .map(|line| {
+                line.trim_matches(|c: char| c.is_whitespace() || "#*_>".contains(c))
+                    .to_lowercase()
+            })
+            .collect();
+
+        self.required_sections
+            .iter()
+            .filter(|section| {
+                let section = section.trim().to_lowercase();
+                !headers.iter().any(|header| {
+                    header.trim_end_matches(':') == section
+                        || header.starts_with(&format!("{}:", section))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
     pub fn member_in_project(&self, member: &Member) -> bool {
         if self.associated_roles.is_empty() {
             return true;
@@ -334,135 +652,233 @@ impl Project {
         }
     }
 
-    async fn update_stat_post(&mut self, ctx: &Context) {
-        if let Some(stat_channel) = self.stat_channel {
-            let stat_channel = match fetch_channel(&ctx, stat_channel) {
-                Ok(channel) => channel,
-                Err(e) => {
-                    Logger::error(
-                        "project.update_stat_post",
-                        &format!(
-                            "cannot fetch stat channel {} of project \"{}\": {}",
-                            stat_channel.get(),
-                            self.name(),
-                            e
-                        ),
-                    )
-                    .await;
-                    return;
-                }
-            };
-
-            let embeds = self.get_stat_embeds(&ctx).await;
+    async fn update_stat_post(
+        &self,
+        ctx: &Context,
+        view: &StatView,
+    ) -> Option<HashMap<RoleId, Vec<MessageId>>> {
+        let stat_channel = self.stat_channel?;
+        let stat_channel = match fetch_channel(&ctx, stat_channel) {
+            Ok(channel) => channel,
+            Err(e) => {
+                Logger::error(
+                    "project.update_stat_post",
+                    &format!(
+                        "cannot fetch stat channel {} of project \"{}\": {}",
+                        stat_channel.get(),
+                        self.name(),
+                        e
+                    ),
+                )
+                .await;
+                return None;
+            }
+        };
+
+        let mut embeds = self.get_stat_embeds(view).await;
+        let ordered: Vec<(RoleId, Vec<CreateEmbed>)> = self
+            .associated_roles
+            .iter()
+            .filter_map(|role| Some((*role, embeds.remove(role)?)))
+            .collect();
+
+        let mut hashes = STATHASHES.write().await;
+        let mut edited = 0;
+
+        let same_layout = ordered.len() == self.stat_posts.len()
+            && ordered.iter().all(|(role, role_embeds)| {
+                self.stat_posts
+                    .get(role)
+                    .is_some_and(|posts| posts.len() == role_embeds.len())
+            });
+
+        if same_layout {
+            let mut failed = false;
+
+            'edit: for (role, role_embeds) in ordered.iter() {
+                for (index, embed) in role_embeds.iter().enumerate() {
+                    let hash = stat_hash(embed);
+                    let key = (self.name.clone(), *role, index);
+
+                    if hashes.get(&key) == Some(&hash) {
+                        continue;
+                    }
 
-            for (role, embed) in embeds.iter() {
-                match self.stat_posts.get(&role) {
-                    Some(msg) => match stat_channel.message(&ctx.http, msg).await {
-                        Ok(mut post) => {
-                            post.edit(&ctx.http, EditMessage::new().embed(embed.clone()))
-                                .await
-                                .unwrap();
-                        }
-                        Err(_) => {
-                            let stat_msg = stat_channel
-                                .send_message(&ctx.http, CreateMessage::new().embed(embed.clone()))
-                                .await
-                                .unwrap();
-
-                            self.stat_posts.insert(role.clone(), stat_msg.id);
-                            self.write().await;
-                        }
-                    },
-                    None => {
-                        let stat_msg = stat_channel
-                            .send_message(&ctx.http, CreateMessage::new().embed(embed.clone()))
+                    let msg = self.stat_posts[role][index];
+                    let edit = match stat_channel.message(&ctx.http, msg).await {
+                        Ok(mut post) => post
+                            .edit(&ctx.http, EditMessage::new().embed(embed.clone()))
                             .await
-                            .unwrap();
+                            .is_ok(),
+                        Err(_) => false,
+                    };
 
-                        self.stat_posts.insert(role.clone(), stat_msg.id);
-                        self.write().await;
+                    if !edit {
+                        failed = true;
+                        break 'edit;
                     }
+
+                    hashes.insert(key, hash);
+                    edited += 1;
                 }
             }
 
-            for (role, msg) in self.stat_posts.clone().iter() {
-                if !self.associated_roles.contains(&role) {
-                    if let Ok(message) = stat_channel.message(&ctx.http, msg).await {
-                        message.delete(&ctx.http).await.unwrap();
+            if !failed {
+                drop(hashes);
+                self.log_stat_update(edited).await;
+                return None;
+            }
+        }
+
+        for (role, msgs) in self.stat_posts.iter() {
+            for (index, msg) in msgs.iter().enumerate() {
+                delete_stat_post(ctx, stat_channel.id, *msg).await;
+                hashes.remove(&(self.name.clone(), *role, index));
+            }
+        }
+
+        let mut posts: HashMap<RoleId, Vec<MessageId>> = HashMap::new();
+        'send: for (role, role_embeds) in ordered.iter() {
+            for (index, embed) in role_embeds.iter().enumerate() {
+                match stat_channel
+                    .send_message(&ctx.http, CreateMessage::new().embed(embed.clone()))
+                    .await
+                {
+                    Ok(stat_msg) => {
+                        posts.entry(*role).or_default().push(stat_msg.id);
+                        hashes.insert((self.name.clone(), *role, index), stat_hash(embed));
+                        edited += 1;
+                    }
+                    Err(e) => {
+                        Logger::error(
+                            "project.update_stat_post",
+                            &format!(
+                                "cannot send stat post of project \"{}\": {}",
+                                self.name(),
+                                e
+                            ),
+                        )
+                        .await;
+                        break 'send;
                     }
-                    self.stat_posts.remove(role);
                 }
             }
+        }
+        drop(hashes);
 
-            Logger::debug(&format!("projects.{}", self.name), "updated stat post").await;
+        self.log_stat_update(edited).await;
+
+        match posts == self.stat_posts {
+            true => None,
+            false => Some(posts),
         }
     }
 
-    async fn get_stat_embeds(&self, ctx: &Context) -> HashMap<RoleId, CreateEmbed> {
-        let mut fields = HashMap::new();
-        let guild = get_guild();
-        let roles = guild.roles(&ctx.http).await.unwrap();
+    async fn log_stat_update(&self, edited: usize) {
+        if edited > 0 {
+            Logger::debug(
+                &format!("projects.{}", self.name),
+                &format!("updated {} stat posts", edited),
+            )
+            .await;
+        }
+    }
 
-        for member in guild.members(&ctx.http, None, None).await.unwrap() {
-            match get_highest_role_in(&ctx, member.user.id, &self.associated_roles).await {
-                Ok(highest) => {
-                    if let Some(role) = highest {
-                        match MEMBERSMANAGER
-                            .write()
-                            .await
-                            .get(member.user.id)
-                            .await
-                            .unwrap()
-                            .to_project_stat(member.display_name().to_string(), &self.name)
-                        {
-                            Ok((name, value, inline)) => {
-                                if !fields.contains_key(&role) {
-                                    fields.insert(role, Vec::new());
-                                }
-
-                                fields.get_mut(&role).unwrap().push((name, value, inline));
-                            }
-                            Err(e) => {
-                                Logger::error(
-                                    "project.get_stat_embeds",
-                                    &format!(
-                                        "cannot get member {} stat post, {}",
-                                        member.display_name(),
-                                        e
-                                    ),
-                                )
-                                .await;
-                            }
-                        }
-                    }
-                }
-                Err(e) => {
-                    Logger::error(
-                        "project.get_stat_embeds",
-                        &format!(
-                            "cannot get highest role of member {} ({}) in project \"{}\": {}",
-                            member.display_name(),
-                            member.user.id.get(),
-                            self.name,
-                            e
-                        ),
-                    )
-                    .await
+    async fn get_stat_embeds(&self, view: &StatView) -> HashMap<RoleId, Vec<CreateEmbed>> {
+        let template = StatTemplate::load(&self.name).await;
+        let roles = &view.roles;
+
+        let mut rows = Vec::new();
+        {
+            let task_man = TASKMANAGER.read().await;
+            let mem_man = MEMBERSMANAGER.read().await;
+
+            for member in view.members.iter() {
+                let mut member_roles: Vec<RoleId> = self
+                    .associated_roles
+                    .iter()
+                    .filter(|role| member.roles.contains(role) && roles.contains_key(role))
+                    .cloned()
+                    .collect();
+
+                if member_roles.is_empty() {
+                    continue;
                 }
+
+                member_roles.sort_by_key(|role| std::cmp::Reverse(roles[role].position));
+
+                let groups = match template.group {
+                    StatGroup::HighestRole => Vec::from([member_roles[0]]),
+                    StatGroup::EachRole => member_roles,
+                    StatGroup::Single => Vec::from([self.associated_roles[0]]),
+                };
+
+                let Some(project_member) = mem_man.find(member.user.id) else {
+                    continue;
+                };
+
+                rows.push((
+                    groups,
+                    member.display_name().to_lowercase(),
+                    project_member.project_score(&self.name),
+                    project_member
+                        .last_activity
+                        .get(&self.name)
+                        .map(|x| x.unix_timestamp())
+                        .unwrap_or(0),
+                    template.field(&project_member.stat_values(
+                        member.display_name().to_string(),
+                        &self.name,
+                        &task_man,
+                    )),
+                ));
+            }
+        }
+
+        match template.sort {
+            StatSort::Name => rows.sort_by(|a, b| a.1.cmp(&b.1)),
+            StatSort::Score => rows.sort_by_key(|row| std::cmp::Reverse(row.2)),
+            StatSort::Activity => rows.sort_by_key(|row| std::cmp::Reverse(row.3)),
+        }
+
+        let mut fields: HashMap<RoleId, Vec<(String, String, bool)>> = HashMap::new();
+        for (groups, _, _, _, field) in rows {
+            for role in groups {
+                fields.entry(role).or_default().push(field.clone());
             }
         }
 
         let mut embeds = HashMap::new();
         for role in self.associated_roles.iter() {
+            let Some(guild_role) = roles.get(role) else {
+                continue;
+            };
+
+            if template.group == StatGroup::Single && role != &self.associated_roles[0] {
+                continue;
+            }
+
+            let fields = match fields.remove(role) {
+                Some(f) => f,
+                None => vec![(String::new(), String::new(), false)],
+            };
+
             embeds.insert(
                 role.clone(),
-                CreateEmbed::new()
-                    .title(format!("{}", roles.get(role).unwrap().name))
-                    .color(roles.get(role).unwrap().colour)
-                    .fields(match fields.get(&role) {
-                        Some(f) => f.clone(),
-                        None => vec![(String::new(), String::new(), false)],
-                    }),
+                split_stat_fields(fields)
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, fields)| {
+                        let embed = CreateEmbed::new().color(guild_role.colour).fields(fields);
+                        match index {
+                            0 => embed.title(match template.group {
+                                StatGroup::Single => self.name.clone(),
+                                _ => guild_role.name.clone(),
+                            }),
+                            _ => embed,
+                        }
+                    })
+                    .collect(),
             );
         }
         embeds
@@ -473,6 +889,20 @@ impl Project {
             .title(loc!("project-embed-title", "project" = self.name()))
             .colour(Colour::MAGENTA);
 
+        embed = embed.field(loc!("project-embed-id-name"), self.id.to_string(), true);
+
+        if let Some(archive) = &self.archive {
+            embed = embed.field(
+                loc!("project-embed-archive-name"),
+                loc!(
+                    "project-embed-archive",
+                    "time" = archive.time.unix_timestamp(),
+                    "member" = archive.by.get()
+                ),
+                true,
+            );
+        }
+
         embed = embed.field(
             loc!("project-embed-max-tasks-per-user-name"),
             self.max_tasks_per_user.to_string(),
@@ -493,6 +923,45 @@ impl Project {
             false,
         );
 
+        let forums: Vec<String> = self
+            .forums
+            .iter()
+            .filter(|x| x.channel != self.tasks_forum)
+            .map(|x| x.describe())
+            .collect();
+
+        if !forums.is_empty() {
+            embed = embed.field(
+                loc!("project-embed-forums-name"),
+                truncate_text(forums.join("\n"), 1024),
+                false,
+            );
+        }
+
+        let managers: Vec<String> = self
+            .managers
+            .iter()
+            .map(|x| {
+                format!(
+                    "<@&{}> — {}",
+                    x.role.get(),
+                    x.permissions
+                        .iter()
+                        .map(|x| x.name())
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                )
+            })
+            .collect();
+
+        if !managers.is_empty() {
+            embed = embed.field(
+                loc!("project-embed-managers-name"),
+                truncate_text(managers.join("\n"), 1024),
+                false,
+            );
+        }
+
         if let Some(channel) = &self.stat_channel {
             embed = embed.field(
                 loc!("project-embed-stat-channel-name"),
@@ -501,6 +970,54 @@ impl Project {
             );
         }
 
+        if !self.required_sections.is_empty() {
+            embed = embed.field(
+                loc!("project-embed-required-sections-name"),
+                self.required_sections
+                    .iter()
+                    .map(|x| format!("- {}", x))
+                    .collect::<Vec<String>>()
+                    .join("\n"),
+                false,
+            );
+        }
+
+        if let Some(policy) = &self.inactivity {
+            embed = embed.field(
+                loc!("project-embed-inactivity-name"),
+                match policy.downgrade_role {
+                    Some(role) => loc!(
+                        "project-embed-inactivity-downgrade",
+                        "days" = policy.days,
+                        "grace" = policy.grace_days,
+                        "role" = role.get()
+                    ),
+                    None => loc!(
+                        "project-embed-inactivity-remove",
+                        "days" = policy.days,
+                        "grace" = policy.grace_days
+                    ),
+                },
+                false,
+            );
+        }
+
+        if let Some(policy) = &self.digest {
+            embed = embed.field(
+                loc!("project-embed-digest-name"),
+                loc!(
+                    "project-embed-digest",
+                    "days" = policy.period_days,
+                    "stale" = policy.stale_days,
+                    "channel" = match policy.channel.or(self.stat_channel) {
+                        Some(channel) => format!("<#{}>", channel.get()),
+                        None => loc!("project-embed-digest-no-channel"),
+                    }
+                ),
+                false,
+            );
+        }
+
         if !self.associated_roles.is_empty() {
             embed = embed.field(
                 loc!(
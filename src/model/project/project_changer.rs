@@ -7,7 +7,15 @@ use serenity::{
 
 impl Project {
     pub async fn main_changer(&self) -> Vec<CreateActionRow> {
-        let mut rows = get_params_buttons("project-changer", Vec::from(["max-tasks-per-user"]));
+        let mut rows = get_compact_params_buttons(
+            "project-changer",
+            Vec::from([
+                "max-tasks-per-user",
+                "required-sections",
+                "inactivity",
+                "digest",
+            ]),
+        );
 
         rows.push(CreateActionRow::SelectMenu(
             CreateSelectMenu::new(
@@ -0,0 +1,174 @@
+use crate::{model::member::ProjectMember, model::task::TaskManager, prelude::*};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatSort {
+    #[default]
+    Name,
+    Score,
+    Activity,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub enum StatGroup {
+    #[default]
+    HighestRole,
+    EachRole,
+    Single,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StatTemplate {
+    #[serde(default = "default_title")]
+    pub title: String,
+    #[serde(default = "default_body")]
+    pub body: String,
+    #[serde(default = "default_inline")]
+    pub inline: bool,
+    #[serde(default)]
+    pub sort: StatSort,
+    #[serde(default)]
+    pub group: StatGroup,
+}
+
+fn default_title() -> String {
+    "{name}".to_string()
+}
+
+fn default_body() -> String {
+    format!(
+        "╠︎ **{}:** {{done}}\n╠︎ **{}:** {{mentored}}\n╠︎ **{}:** {{in_tasks}}\n╠︎ **{}:** {{last_activity}}\n╚ **{}:** {{score}}",
+        loc!("member-project-stat-done-tasks-name"),
+        loc!("member-project-stat-mentor-tasks-name"),
+        loc!("member-project-stat-in-tasks-name"),
+        loc!("member-project-stat-last-activity-name"),
+        loc!("member-project-stat-score-name"),
+    )
+}
+
+fn default_inline() -> bool {
+    true
+}
+
+impl Default for StatTemplate {
+    fn default() -> Self {
+        Self {
+            title: default_title(),
+            body: default_body(),
+            inline: default_inline(),
+            sort: StatSort::default(),
+            group: StatGroup::default(),
+        }
+    }
+}
+
+impl StatTemplate {
+    pub async fn load(project: &str) -> Self {
+        let path = DATA_PATH.join(format!("stat_templates/{}.yml", project));
+
+        if !fs::exists(&path).unwrap_or(false) {
+            return Self::default();
+        }
+
+        match serde_yaml::from_str(&read_file(&path)) {
+            Ok(template) => template,
+            Err(e) => {
+                Logger::error(
+                    "stat_template.load",
+                    &format!(
+                        "error while parsing stat template of project \"{}\": {}",
+                        project, e
+                    ),
+                )
+                .await;
+                Self::default()
+            }
+        }
+    }
+
+    pub fn render(text: &str, values: &HashMap<&str, String>) -> String {
+        let mut text = text.to_string();
+
+        for (key, value) in values.iter() {
+            text = text.replace(&format!("{{{}}}", key), value);
+        }
+
+        text
+    }
+
+    pub fn field(&self, values: &HashMap<&str, String>) -> (String, String, bool) {
+        (
+            truncate_text(Self::render(&self.title, values), 256),
+            truncate_text(Self::render(&self.body, values), 1024),
+            self.inline,
+        )
+    }
+}
+
+pub const STAT_POST_FIELDS: usize = 25;
+const STAT_POST_LIMIT: usize = 5500;
+
+pub fn split_stat_fields(fields: Vec<(String, String, bool)>) -> Vec<Vec<(String, String, bool)>> {
+    split_pages(fields, STAT_POST_FIELDS, STAT_POST_LIMIT, |field| {
+        field.0.chars().count() + field.1.chars().count()
+    })
+}
+
+impl ProjectMember {
+    pub fn stat_values(
+        &self,
+        member_name: String,
+        project_name: &String,
+        task_man: &TaskManager,
+    ) -> HashMap<&'static str, String> {
+        let in_tasks = self.in_tasks.get(project_name).cloned().unwrap_or_default();
+
+        HashMap::from([
+            ("name", member_name),
+            ("mention", format!("<@{}>", self.id.get())),
+            (
+                "done",
+                self.done_tasks
+                    .get(project_name)
+                    .map(|x| x.len())
+                    .unwrap_or(0)
+                    .to_string(),
+            ),
+            (
+                "mentored",
+                self.mentor_tasks
+                    .get(project_name)
+                    .map(|x| x.len())
+                    .unwrap_or(0)
+                    .to_string(),
+            ),
+            ("in_tasks_count", in_tasks.len().to_string()),
+            (
+                "in_tasks",
+                match in_tasks.is_empty() {
+                    true => loc!("member-project-stat-no-in-tasks"),
+                    false => in_tasks
+                        .iter()
+                        .filter_map(|id| task_man.get(*id))
+                        .map(|task| format!("\n╠︎ <#{}>", task.thread_id.get()))
+                        .collect(),
+                },
+            ),
+            (
+                "last_activity",
+                match self.last_activity.get(project_name) {
+                    Some(activity) => format!("<t:{}:R>", activity.timestamp()),
+                    None => loc!("member-project-stat-no-last-activity"),
+                },
+            ),
+            ("score", self.project_score(project_name).to_string()),
+            ("total_score", self.total_score().to_string()),
+            ("all_time_score", self.all_time_score.to_string()),
+        ])
+    }
+}
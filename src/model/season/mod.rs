@@ -0,0 +1,5 @@
+mod season;
+
+pub use season::{
+    season_listener, CurrentSeason, Season, SeasonEnd, SeasonManager, SeasonMember, SEASONMANAGER,
+};
@@ -0,0 +1,473 @@
+use crate::{
+    model::member::{ProjectMember, TaskHistory, MEMBERSMANAGER},
+    prelude::*,
+};
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use serenity::{
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed},
+    model::{id::UserId, timestamp::Timestamp},
+};
+use std::fs;
+use std::{collections::HashMap, sync::Arc};
+use tokio::sync::RwLock;
+use walkdir::WalkDir;
+
+pub static SEASONMANAGER: Lazy<Arc<RwLock<SeasonManager>>> =
+    Lazy::new(|| Arc::new(RwLock::new(SeasonManager::new())));
+
+const RESULTS_TOP: usize = 15;
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SeasonMember {
+    pub id: UserId,
+    pub score: i64,
+    pub all_time_score: i64,
+    pub done_tasks: HashMap<String, usize>,
+    pub mentor_tasks: HashMap<String, usize>,
+}
+
+impl SeasonMember {
+    fn snapshot(member: &ProjectMember, since: Option<Timestamp>) -> Self {
+        Self {
+            id: member.id,
+            score: member.total_score(),
+            all_time_score: member.all_time_score,
+            done_tasks: count_since(&member.done_tasks, since),
+            mentor_tasks: count_since(&member.mentor_tasks, since),
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.score == 0 && self.done_tasks.is_empty() && self.mentor_tasks.is_empty()
+    }
+}
+
+fn count_since(
+    tasks: &HashMap<String, Vec<TaskHistory>>,
+    since: Option<Timestamp>,
+) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+
+    for (project, history) in tasks.iter() {
+        let mut count = 0;
+
+        for task in history.iter() {
+            count += match (task, since) {
+                (TaskHistory::Current(map), Some(since)) => map
+                    .keys()
+                    .filter(|time| time.unix_timestamp() >= since.unix_timestamp())
+                    .count(),
+                (TaskHistory::Current(map), None) => map.len(),
+                (TaskHistory::OldFormat(_), Some(_)) => 0,
+                (TaskHistory::OldFormat(_), None) => 1,
+            };
+        }
+
+        if count > 0 {
+            counts.insert(project.clone(), count);
+        }
+    }
+
+    counts
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct Season {
+    pub id: u32,
+    pub name: String,
+    pub started: Option<Timestamp>,
+    pub ended: Timestamp,
+    pub decay: f64,
+    pub members: Vec<SeasonMember>,
+}
+
+impl Season {
+    async fn write(&self) {
+        write_file(
+            &DATA_PATH.join(format!("archives/seasons/{}", self.id)),
+            match serde_json::to_string(&self) {
+                Ok(content) => content,
+                Err(e) => {
+                    Logger::error(
+                        "season.write",
+                        &format!("cannot serialize season \"{}\": {}", self.name, e),
+                    )
+                    .await;
+                    return;
+                }
+            },
+        );
+    }
+
+    pub fn to_embed(&self, others: Vec<&String>) -> CreateEmbed {
+        let mut members: Vec<&SeasonMember> = self.members.iter().collect();
+        members.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+
+        let mut top = Vec::new();
+        for (index, member) in members.iter().take(RESULTS_TOP).enumerate() {
+            top.push(format!(
+                "`#{}` <@{}> — **{}**",
+                index + 1,
+                member.id.get(),
+                member.score
+            ));
+        }
+
+        let mut projects: HashMap<&String, (usize, usize)> = HashMap::new();
+        for member in self.members.iter() {
+            for (project, count) in member.done_tasks.iter() {
+                projects.entry(project).or_default().0 += count;
+            }
+
+            for (project, count) in member.mentor_tasks.iter() {
+                projects.entry(project).or_default().1 += count;
+            }
+        }
+
+        let mut projects: Vec<(&String, (usize, usize))> = projects.into_iter().collect();
+        projects.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut embed = CreateEmbed::new()
+            .title(loc!("season-embed-title", "season" = self.name))
+            .color(Colour::GOLD)
+            .description(loc!(
+                "season-embed-description",
+                "started" = match self.started {
+                    Some(date) => format!("<t:{}:D>", date.unix_timestamp()),
+                    None => loc!("season-embed-no-start"),
+                },
+                "ended" = format!("<t:{}:D>", self.ended.unix_timestamp()),
+                "members" = self.members.len(),
+                "decay" = self.decay
+            ))
+            .field(
+                loc!("season-embed-top-name"),
+                match top.is_empty() {
+                    true => loc!("season-embed-empty"),
+                    false => truncate_text(top.join("\n"), 1024),
+                },
+                false,
+            );
+
+        if !projects.is_empty() {
+            embed = embed.field(
+                loc!("season-embed-projects-name"),
+                truncate_text(
+                    box_list(
+                        projects
+                            .iter()
+                            .map(|(project, (done, mentor))| {
+                                loc!(
+                                    "season-embed-project",
+                                    "project" = project,
+                                    "done" = done,
+                                    "mentor" = mentor
+                                )
+                            })
+                            .collect(),
+                    ),
+                    1024,
+                ),
+                false,
+            );
+        }
+
+        if !others.is_empty() {
+            embed = embed.field(
+                loc!("season-embed-others-name"),
+                truncate_text(
+                    others
+                        .iter()
+                        .map(|x| format!("`{}`", x))
+                        .collect::<Vec<String>>()
+                        .join(", "),
+                    1024,
+                ),
+                false,
+            );
+        }
+
+        embed
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct CurrentSeason {
+    pub name: Option<String>,
+    pub started: Option<Timestamp>,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct SeasonEnd {
+    pub name: String,
+    pub next_name: Option<String>,
+    pub decay: f64,
+}
+
+impl SeasonEnd {
+    pub fn to_embed(&self, members: usize) -> CreateEmbed {
+        CreateEmbed::new()
+            .title(loc!("season-end-embed-title", "season" = self.name))
+            .color(Colour::RED)
+            .description(loc!(
+                "season-end-embed-description",
+                "members" = members,
+                "decay" = self.decay,
+                "next" = self.next_name.clone().unwrap_or(loc!("season-no-name"))
+            ))
+    }
+
+    pub fn confirm_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("season-end:confirm")
+                .label(loc!("season-end-confirm-button"))
+                .style(ButtonStyle::Danger),
+            CreateButton::new("season-end:cancel")
+                .label(loc!("season-end-cancel-button"))
+                .style(ButtonStyle::Secondary),
+        ]))])
+    }
+}
+
+#[derive(Debug)]
+pub struct SeasonManager {
+    seasons: HashMap<u32, Season>,
+    current: CurrentSeason,
+    last_season_id: u32,
+}
+
+impl SeasonManager {
+    fn new() -> Self {
+        let content = read_file(&DATA_PATH.join("season.json"));
+
+        Self {
+            seasons: HashMap::new(),
+            current: match serde_json::from_str(&content) {
+                Ok(c) => c,
+                Err(_) => CurrentSeason {
+                    name: None,
+                    started: None,
+                },
+            },
+            last_season_id: 0,
+        }
+    }
+
+    pub async fn init(&mut self) {
+        if !fs::exists(DATA_PATH.join("archives/seasons")).unwrap() {
+            fs::create_dir_all(DATA_PATH.join("archives/seasons"))
+                .expect("error while creating folder data/archives/seasons");
+        }
+
+        for entry in WalkDir::new(DATA_PATH.join("archives/seasons")) {
+            let entry = match entry {
+                Ok(s) => s,
+                Err(error) => {
+                    Logger::error(
+                        "season_man.init",
+                        &format!("error with season archive file: {}", error),
+                    )
+                    .await;
+                    continue;
+                }
+            };
+
+            if !entry.path().is_file() {
+                continue;
+            }
+
+            let season: Season =
+                match serde_json::from_str(read_file(&entry.path().to_path_buf()).as_str()) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        Logger::error(
+                            "season_man.init",
+                            &format!(
+                                "error while parsing season archive file \"{}\": {}",
+                                entry.file_name().to_str().unwrap(),
+                                e
+                            ),
+                        )
+                        .await;
+                        continue;
+                    }
+                };
+
+            if self.last_season_id < season.id {
+                self.last_season_id = season.id;
+            }
+
+            self.seasons.insert(season.id, season);
+        }
+
+        Logger::debug("season_man.init", "initialized from archives/seasons/*").await;
+    }
+
+    async fn write_current(&self) {
+        write_file(
+            &DATA_PATH.join("season.json"),
+            match serde_json::to_string(&self.current) {
+                Ok(c) => c,
+                Err(e) => {
+                    Logger::error("season_man.write_current", e.to_string().as_str()).await;
+                    return;
+                }
+            },
+        );
+    }
+
+    pub fn current(&self) -> &CurrentSeason {
+        &self.current
+    }
+
+    pub fn get_by_name(&self, name: &String) -> Option<&Season> {
+        self.seasons.values().find(|season| &season.name == name)
+    }
+
+    pub fn latest(&self) -> Option<&Season> {
+        self.seasons.get(&self.last_season_id)
+    }
+
+    pub fn names(&self) -> Vec<&String> {
+        let mut seasons: Vec<&Season> = self.seasons.values().collect();
+        seasons.sort_by_key(|season| season.id);
+        seasons.iter().map(|season| &season.name).collect()
+    }
+
+    pub async fn end_season(&mut self, end: SeasonEnd) -> u32 {
+        let mut mem_man = MEMBERSMANAGER.write().await;
+
+        let members: Vec<SeasonMember> = mem_man
+            .get_all()
+            .into_iter()
+            .map(|member| SeasonMember::snapshot(member, self.current.started))
+            .filter(|member| !member.is_empty())
+            .collect();
+
+        for snapshot in members.iter() {
+            if let Ok(member) = mem_man.get_mut(snapshot.id).await {
+                member.decay_score(end.decay).await;
+            }
+        }
+        drop(mem_man);
+
+        self.last_season_id += 1;
+
+        let season = Season {
+            id: self.last_season_id,
+            name: end.name.clone(),
+            started: self.current.started,
+            ended: Timestamp::now(),
+            decay: end.decay,
+            members,
+        };
+        season.write().await;
+
+        Logger::high(
+            "season_man.end_season",
+            &format!(
+                "season \"{}\" ended with {} members, scores decayed by {}",
+                season.name,
+                season.members.len(),
+                season.decay
+            ),
+        )
+        .await;
+
+        self.seasons.insert(season.id, season);
+
+        self.current = CurrentSeason {
+            name: end.next_name,
+            started: Some(Timestamp::now()),
+        };
+        self.write_current().await;
+
+        self.last_season_id
+    }
+
+    pub async fn replace_user(&mut self, from: UserId, into: Option<UserId>) -> usize {
+        let mut changed = 0;
+
+        for season in self.seasons.values_mut() {
+            let Some(index) = season.members.iter().position(|x| x.id == from) else {
+                continue;
+            };
+
+            match into {
+                Some(id) => season.members[index].id = id,
+                None => {
+                    season.members.remove(index);
+                }
+            }
+            season.write().await;
+            changed += 1;
+        }
+
+        changed
+    }
+}
+
+pub async fn season_listener() {
+    #[listen_component("season-end:confirm")]
+    async fn confirm(ctx: &Context, inter: ComponentInteraction) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let end = MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .season_end
+            .take();
+
+        let Some(end) = end else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("season-end-no-operation")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let mut season_man = SEASONMANAGER.write().await;
+        let id = season_man.end_season(end).await;
+        let season = season_man.seasons.get(&id).unwrap();
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(season.to_embed(Vec::new())),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("season-end:cancel")]
+    async fn cancel(ctx: &Context, inter: ComponentInteraction) {
+        MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .season_end = None;
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("season-end-cancelled"))
+                        .embeds(Vec::new())
+                        .components(Vec::new()),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+}
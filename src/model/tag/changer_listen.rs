@@ -26,6 +26,7 @@ pub async fn tag_changer_listener() {
                         "base" => Some(TageTypes::Base),
                         "closedtask" => Some(TageTypes::ClosedTask),
                         "inwork" => Some(TageTypes::InWork),
+                        "needsinfo" => Some(TageTypes::NeedsInfo),
                         _ => None,
                     })
                     .await;
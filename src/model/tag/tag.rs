@@ -16,6 +16,7 @@ pub enum TageTypes {
     Base,
     ClosedTask,
     InWork,
+    NeedsInfo,
 }
 
 impl ToString for TageTypes {
@@ -24,6 +25,7 @@ impl ToString for TageTypes {
             Self::Base => String::from("base"),
             Self::ClosedTask => String::from("closedtask"),
             Self::InWork => String::from("inwork"),
+            Self::NeedsInfo => String::from("needsinfo"),
         }
     }
 }
@@ -132,6 +134,20 @@ impl TagsManager {
         Some(tags)
     }
 
+    pub async fn rename_project(&mut self, from: &str, into: &str) -> usize {
+        let mut renamed = 0;
+
+        for tag in self.tags.values_mut() {
+            if tag.task_project.as_deref() == Some(from) {
+                tag.task_project = Some(into.to_string());
+                tag.update().await;
+                renamed += 1;
+            }
+        }
+
+        renamed
+    }
+
     pub async fn new_tag(&mut self, tag_id: ForumTagId, forum_id: ChannelId) {
         let tag = TaskTag::new(tag_id, forum_id);
         self.tags.insert(tag.id, tag.clone());
@@ -23,6 +23,8 @@ impl TaskTag {
                                 .default_selection(self.tag_type == Some(TageTypes::ClosedTask)),
                             CreateSelectMenuOption::new(loc!("tag-types-inwork"), "inwork")
                                 .default_selection(self.tag_type == Some(TageTypes::InWork)),
+                            CreateSelectMenuOption::new(loc!("tag-types-needsinfo"), "needsinfo")
+                                .default_selection(self.tag_type == Some(TageTypes::NeedsInfo)),
                         ]),
                     },
                 )
@@ -0,0 +1,450 @@
+use std::collections::HashMap;
+
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use serenity::{
+    self,
+    all::{ButtonStyle, Colour, CreateActionRow, CreateButton, CreateEmbed, Timestamp, UserId},
+};
+
+use super::{Task, TASKMANAGER};
+
+const PREVIEW_LIMIT: usize = 30;
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+pub enum TaskState {
+    Any,
+    Open,
+    Finished,
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TaskFilter {
+    pub project: Option<String>,
+    pub tag: Option<String>,
+    pub state: TaskState,
+    pub member: Option<UserId>,
+    pub older_than_days: Option<i64>,
+}
+
+impl TaskFilter {
+    async fn matches(&self, ctx: &Context, task: &Task) -> bool {
+        if let Some(project) = &self.project {
+            if &task.project != project {
+                return false;
+            }
+        }
+
+        match self.state {
+            TaskState::Open if task.finished => return false,
+            TaskState::Finished if !task.finished => return false,
+            _ => (),
+        }
+
+        if let Some(member) = &self.member {
+            if !task.members.get().contains(member) && task.mentor_id.get() != &Some(*member) {
+                return false;
+            }
+        }
+
+        if let Some(days) = self.older_than_days {
+            match task.start_date {
+                Some(date) => {
+                    if Timestamp::now().unix_timestamp() - date.unix_timestamp() < days * 86400 {
+                        return false;
+                    }
+                }
+                None => return false,
+            }
+        }
+
+        if let Some(tag_name) = &self.tag {
+            let Some(thread) = fetch_task_thread(ctx, task).await else {
+                return false;
+            };
+
+            let Some(forum) = thread
+                .parent_id
+                .and_then(|forum| fetch_channel(ctx, forum).ok())
+            else {
+                return false;
+            };
+
+            if !forum
+                .available_tags
+                .iter()
+                .any(|tag| &tag.name == tag_name && thread.applied_tags.contains(&tag.id))
+            {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    fn describe(&self) -> String {
+        let mut lines = Vec::new();
+
+        if let Some(project) = &self.project {
+            lines.push(loc!("tasks-bulk-filter-project", "project" = project));
+        }
+
+        if let Some(tag) = &self.tag {
+            lines.push(loc!("tasks-bulk-filter-tag", "tag" = tag));
+        }
+
+        lines.push(loc!(
+            "tasks-bulk-filter-state",
+            "state" = loc!(match self.state {
+                TaskState::Any => "tasks-bulk-state-all",
+                TaskState::Open => "tasks-bulk-state-open",
+                TaskState::Finished => "tasks-bulk-state-finished",
+            })
+        ));
+
+        if let Some(member) = &self.member {
+            lines.push(loc!("tasks-bulk-filter-member", "member" = member.get()));
+        }
+
+        if let Some(days) = self.older_than_days {
+            lines.push(loc!("tasks-bulk-filter-age", "days" = days));
+        }
+
+        box_list(lines)
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub enum BulkAction {
+    Close,
+    Reopen,
+    SetMentor(Option<UserId>),
+    SetScore(i64),
+    RemoveMember(UserId),
+    FetchTags,
+}
+
+impl BulkAction {
+    fn describe(&self) -> String {
+        match self {
+            Self::Close => loc!("tasks-bulk-action-close"),
+            Self::Reopen => loc!("tasks-bulk-action-reopen"),
+            Self::SetMentor(Some(id)) => {
+                format!("{} <@{}>", loc!("tasks-bulk-action-set-mentor"), id.get())
+            }
+            Self::SetMentor(None) => format!(
+                "{} {}",
+                loc!("tasks-bulk-action-set-mentor"),
+                loc!("tasks-bulk-no-mentor")
+            ),
+            Self::SetScore(score) => {
+                format!("{} `{}`", loc!("tasks-bulk-action-set-score"), score)
+            }
+            Self::RemoveMember(id) => {
+                format!(
+                    "{} <@{}>",
+                    loc!("tasks-bulk-action-remove-member"),
+                    id.get()
+                )
+            }
+            Self::FetchTags => loc!("tasks-bulk-action-fetch-tags"),
+        }
+    }
+
+    async fn apply(&self, ctx: &Context, task: &mut Task) -> bool {
+        match self {
+            Self::Close => {
+                if task.finished {
+                    return false;
+                }
+
+                task.ending_results = HashMap::new();
+                if let Some(mentor) = task.mentor_id.get() {
+                    task.ending_results.insert(*mentor, 2.0);
+                }
+
+                task.close(ctx).await;
+                task.finished
+            }
+            Self::Reopen => {
+                if !task.finished {
+                    return false;
+                }
+
+                task.open(ctx).await;
+                !task.finished
+            }
+            Self::SetMentor(mentor) => {
+                if task.mentor_id.get() == mentor {
+                    return false;
+                }
+
+                task.set_mentor(ctx, *mentor, true).await
+            }
+            Self::SetScore(score) => {
+                if task.score.get() == score {
+                    return false;
+                }
+
+                task.set_score(ctx, *score).await;
+                true
+            }
+            Self::RemoveMember(member) => {
+                if !task.members.get().contains(member) {
+                    return false;
+                }
+
+                task.remove_member(ctx, *member).await;
+                !task.members.get().contains(member)
+            }
+            Self::FetchTags => {
+                let Some(thread) = fetch_task_thread(ctx, task).await else {
+                    return false;
+                };
+
+                task.fetch_tags(&thread).await;
+                task.update().await;
+                true
+            }
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct BulkOperation {
+    pub filter: TaskFilter,
+    pub action: BulkAction,
+    pub tasks: Vec<u32>,
+}
+
+impl BulkOperation {
+    pub async fn new(ctx: &Context, filter: TaskFilter, action: BulkAction) -> Self {
+        let task_man = TASKMANAGER.read().await;
+
+        let mut tasks = Vec::new();
+        for task in task_man.get_all() {
+            if filter.matches(ctx, task).await {
+                tasks.push(task.id);
+            }
+        }
+        tasks.sort();
+
+        Self {
+            filter,
+            action,
+            tasks,
+        }
+    }
+
+    pub async fn apply(&self, ctx: &Context) -> BulkReport {
+        let mut report = BulkReport::default();
+
+        for id in self.tasks.iter() {
+            let mut task_man = TASKMANAGER.write().await;
+
+            let Some(task) = task_man.get_mut(*id) else {
+                report.missing.push(*id);
+                continue;
+            };
+
+            let line = format!("`{}` {}", task.id, task.name.get());
+
+            if self.action.apply(ctx, task).await {
+                report.applied.push(line);
+            } else {
+                report.skipped.push(line);
+            }
+        }
+
+        Logger::medium(
+            "tasks_bulk.apply",
+            &format!(
+                "bulk action {:?} applied to {} tasks, skipped {}, missing {}",
+                self.action,
+                report.applied.len(),
+                report.skipped.len(),
+                report.missing.len()
+            ),
+        )
+        .await;
+
+        report
+    }
+
+    pub async fn preview_embed(&self) -> CreateEmbed {
+        let task_man = TASKMANAGER.read().await;
+
+        let mut lines = Vec::new();
+        for id in self.tasks.iter().take(PREVIEW_LIMIT) {
+            if let Some(task) = task_man.get(*id) {
+                lines.push(format!(
+                    "`{}` {} (<#{}>)",
+                    task.id,
+                    task.name.get(),
+                    task.thread_id.get()
+                ));
+            }
+        }
+
+        if self.tasks.len() > PREVIEW_LIMIT {
+            lines.push(loc!(
+                "tasks-bulk-and-more",
+                "num" = self.tasks.len() - PREVIEW_LIMIT
+            ));
+        }
+
+        CreateEmbed::new()
+            .title(loc!("tasks-bulk-preview-title", "num" = self.tasks.len()))
+            .color(Colour::ORANGE)
+            .field(
+                loc!("tasks-bulk-filter-name"),
+                self.filter.describe(),
+                false,
+            )
+            .field(
+                loc!("tasks-bulk-action-name"),
+                self.action.describe(),
+                false,
+            )
+            .description(truncate_text(box_list(lines), 4096))
+    }
+
+    pub fn confirm_buttons() -> Vec<CreateActionRow> {
+        Vec::from([CreateActionRow::Buttons(Vec::from([
+            CreateButton::new("tasks-bulk:confirm")
+                .label(loc!("tasks-bulk-confirm-button"))
+                .style(ButtonStyle::Danger),
+            CreateButton::new("tasks-bulk:cancel")
+                .label(loc!("tasks-bulk-cancel-button"))
+                .style(ButtonStyle::Secondary),
+        ]))])
+    }
+}
+
+#[derive(Default, Debug)]
+pub struct BulkReport {
+    pub applied: Vec<String>,
+    pub skipped: Vec<String>,
+    pub missing: Vec<u32>,
+}
+
+impl BulkReport {
+    pub fn to_embed(&self) -> CreateEmbed {
+        let mut embed = CreateEmbed::new()
+            .title(loc!(
+                "tasks-bulk-report-title",
+                "applied" = self.applied.len(),
+                "skipped" = self.skipped.len()
+            ))
+            .color(Colour::DARK_GREEN);
+
+        if !self.applied.is_empty() {
+            embed = embed.field(
+                loc!("tasks-bulk-report-applied-name"),
+                truncate_text(box_list(self.applied.clone()), 1024),
+                false,
+            );
+        }
+
+        if !self.skipped.is_empty() {
+            embed = embed.field(
+                loc!("tasks-bulk-report-skipped-name"),
+                truncate_text(box_list(self.skipped.clone()), 1024),
+                false,
+            );
+        }
+
+        if !self.missing.is_empty() {
+            embed = embed.field(
+                loc!("tasks-bulk-report-missing-name"),
+                self.missing
+                    .iter()
+                    .map(|x| format!("`{}`", x))
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                false,
+            );
+        }
+
+        embed
+    }
+}
+
+async fn fetch_task_thread(ctx: &Context, task: &Task) -> Option<GuildChannel> {
+    if let Ok(thread) = fetch_thread(ctx, task.thread_id) {
+        return Some(thread);
+    }
+
+    match task.thread_id.to_channel(&ctx.http).await {
+        Ok(channel) => channel.guild(),
+        Err(e) => {
+            Logger::debug(
+                "tasks_bulk.fetch_task_thread",
+                &format!("cannot fetch thread of task {}: {}", task.id, e),
+            )
+            .await;
+            None
+        }
+    }
+}
+
+pub async fn tasks_bulk_listener() {
+    #[listen_component("tasks-bulk:confirm")]
+    async fn confirm(ctx: &Context, inter: ComponentInteraction) {
+        inter.defer_ephemeral(&ctx.http).await.unwrap();
+
+        let operation = member::MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .bulk_operation
+            .take();
+
+        let Some(operation) = operation else {
+            inter
+                .edit_response(
+                    &ctx.http,
+                    EditInteractionResponse::new().content(loc!("tasks-bulk-no-operation")),
+                )
+                .await
+                .unwrap();
+            return;
+        };
+
+        let report = operation.apply(ctx).await;
+
+        inter
+            .edit_response(
+                &ctx.http,
+                EditInteractionResponse::new().embed(report.to_embed()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[listen_component("tasks-bulk:cancel")]
+    async fn cancel(ctx: &Context, inter: ComponentInteraction) {
+        member::MEMBERSMANAGER
+            .write()
+            .await
+            .get_mut(inter.user.id)
+            .await
+            .unwrap()
+            .bulk_operation = None;
+
+        inter
+            .create_response(
+                &ctx.http,
+                CreateInteractionResponse::UpdateMessage(
+                    CreateInteractionResponseMessage::new()
+                        .content(loc!("tasks-bulk-cancelled"))
+                        .embeds(Vec::new())
+                        .components(Vec::new()),
+                ),
+            )
+            .await
+            .unwrap();
+    }
+}
@@ -1,6 +1,10 @@
+mod bulk;
 mod changer_listen;
 mod task;
 mod task_changer;
+mod transcript;
 
+pub use bulk::{tasks_bulk_listener, BulkAction, BulkOperation, BulkReport, TaskFilter, TaskState};
 pub use changer_listen::task_changer_listener;
-pub use task::{Task, TaskOption, TASKMANAGER};
+pub use task::{LastSave, Task, TaskManager, TaskOption, TASKMANAGER};
+pub use transcript::{escape_html, TaskTranscript, TranscriptMessage};
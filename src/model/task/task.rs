@@ -1,7 +1,10 @@
 use crate::{
+    connect::SaveFile,
     model::{
         member::MEMBERSMANAGER,
+        project::{Project, ProjectManager, TaskForum},
         tag::{TageTypes, TAGSMANAGER},
+        task::TaskTranscript,
     },
     prelude::*,
 };
@@ -9,7 +12,10 @@ use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
 use serde_json;
 use serenity::{
-    all::{Colour, CreateActionRow, CreateEmbed, CreateSelectMenu, CreateSelectMenuOption},
+    all::{
+        Colour, CreateActionRow, CreateEmbed, CreateForumPost, CreateSelectMenu,
+        CreateSelectMenuOption, ForumTagId, MessageId,
+    },
     builder::{CreateMessage, EditThread},
     client::Context,
     model::{
@@ -18,6 +24,7 @@ use serenity::{
         timestamp::Timestamp,
     },
 };
+use std::fmt;
 use std::fs;
 use std::{collections::HashMap, sync::Arc};
 use tokio::sync::RwLock;
@@ -70,6 +77,38 @@ impl<T: Clone> TaskOption<T> {
         self.value_history.insert(Timestamp::now(), value.clone());
         self.modified_value = Some(value);
     }
+
+    pub fn modified(&self) -> Option<&T> {
+        self.modified_value.as_ref()
+    }
+
+    pub fn map_values(&mut self, mut f: impl FnMut(&mut T)) {
+        f(&mut self.base_value);
+
+        if let Some(ref mut value) = self.modified_value {
+            f(value);
+        }
+
+        for value in self.value_history.values_mut() {
+            f(value);
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum LastSave {
+    Current(SaveFile),
+    OldFormat(String),
+}
+
+impl fmt::Display for LastSave {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LastSave::Current(save) => write!(f, "{} ({}) - {}", save.path(), save.db, save.date),
+            LastSave::OldFormat(string) => write!(f, "{}", string),
+        }
+    }
 }
 
 #[derive(Deserialize, Debug)]
@@ -141,16 +180,9 @@ impl TaskManager {
         ctx: &Context,
         thread: &mut GuildChannel,
         project: String,
-        waiter_role: Option<RoleId>,
+        forum: &TaskForum,
     ) -> Result<u32, String> {
-        let task = Task::new(
-            &ctx,
-            self.last_task_id + 1,
-            project.clone(),
-            waiter_role,
-            thread,
-        )
-        .await?;
+        let task = Task::new(&ctx, self.last_task_id + 1, project.clone(), forum, thread).await?;
         self.last_task_id += 1;
 
         Logger::low(
@@ -168,6 +200,167 @@ impl TaskManager {
         Ok(self.last_task_id)
     }
 
+    pub async fn move_task(
+        &mut self,
+        ctx: &Context,
+        id: u32,
+        project: &Project,
+    ) -> Result<ChannelId, String> {
+        let task = self
+            .tasks
+            .get_mut(&id)
+            .ok_or("task not found".to_string())?;
+
+        if task.finished {
+            return Err("task already finished".to_string());
+        }
+
+        if &task.project == project.name() {
+            return Err("task already in this project".to_string());
+        }
+
+        let old_thread = fetch_thread(ctx, task.thread_id)?;
+        let thread = create_task_post(
+            ctx,
+            task,
+            project.tasks_forum,
+            task.name.get().clone(),
+            loc!("task-moved-from", "thread" = task.thread_id.get()),
+        )
+        .await?;
+
+        let mut mem_man = MEMBERSMANAGER.write().await;
+        for member_id in task.members.get().iter() {
+            if let Ok(member) = mem_man.get_mut(*member_id).await {
+                member.leave_task(task).await;
+            }
+        }
+
+        let old_project = task.project.clone();
+        task.project = project.name().clone();
+        task.thread_id = thread.id;
+        task.fetch_tags(&thread).await;
+        task.project = project.name().clone();
+
+        for member_id in task.members.get().iter() {
+            if let Ok(member) = mem_man.get_mut(*member_id).await {
+                member.join_task(task).await;
+            }
+        }
+        drop(mem_man);
+
+        task.update().await;
+
+        Logger::medium(
+            "tasks_man.move_task",
+            &format!(
+                "task \"{}\" moved from project {} to {}",
+                task.name.get(),
+                old_project,
+                project.name()
+            ),
+        )
+        .await;
+
+        let ping =
+            match task.get_roles_ping(&thread, project.forum(project.tasks_forum).waiter_role) {
+                Some(ping) => format!("{} {}", ping, task.get_members_ping()),
+                None => task.get_members_ping(),
+            };
+
+        Logger::if_ok(
+            "tasks_man.move_task",
+            "cannot send ping message in moved task",
+            thread
+                .send_message(&ctx.http, CreateMessage::new().content(ping))
+                .await,
+        )
+        .await;
+
+        Logger::if_ok(
+            "tasks_man.move_task",
+            "cannot send message about moving task",
+            old_thread
+                .send_message(
+                    &ctx.http,
+                    CreateMessage::new().content(loc!("task-moved-to", "thread" = thread.id.get())),
+                )
+                .await,
+        )
+        .await;
+
+        let mut old_thread = old_thread;
+        Logger::if_ok(
+            "tasks_man.move_task",
+            "cannot lock old thread of moved task",
+            old_thread
+                .edit_thread(&ctx.http, EditThread::new().locked(true).archived(true))
+                .await,
+        )
+        .await;
+
+        Ok(thread.id)
+    }
+
+    pub async fn clone_task(
+        &mut self,
+        ctx: &Context,
+        id: u32,
+        project: &Project,
+        name: Option<String>,
+    ) -> Result<u32, String> {
+        let source = self
+            .tasks
+            .get(&id)
+            .ok_or("task not found".to_string())?
+            .clone();
+
+        let mut thread = create_task_post(
+            ctx,
+            &source,
+            project.tasks_forum,
+            name.unwrap_or(source.name.get().clone()),
+            loc!("task-cloned-from", "thread" = source.thread_id.get()),
+        )
+        .await?;
+
+        let mut task = Task::new(
+            ctx,
+            self.last_task_id + 1,
+            project.name().clone(),
+            &project.forum(project.tasks_forum),
+            &mut thread,
+        )
+        .await?;
+        self.last_task_id += 1;
+
+        if let Some(score) = source.score.modified() {
+            task.score.set(*score);
+        }
+
+        if let Some(max_members) = source.max_members.modified() {
+            task.max_members.set(*max_members);
+        }
+
+        task.last_save.set(source.last_save.get().clone());
+        task.update().await;
+
+        Logger::medium(
+            "tasks_man.clone_task",
+            &format!(
+                "task \"{}\" cloned as task {} in project {}",
+                source.name.get(),
+                task.id,
+                project.name()
+            ),
+        )
+        .await;
+
+        self.tasks.insert(task.id, task);
+
+        Ok(self.last_task_id)
+    }
+
     pub fn get(&self, id: u32) -> Option<&Task> {
         self.tasks.get(&id)
     }
@@ -196,6 +389,10 @@ impl TaskManager {
         None
     }
 
+    pub fn get_all(&self) -> Vec<&Task> {
+        self.tasks.values().collect()
+    }
+
     pub fn get_by_project(&self, project: &String) -> Vec<&Task> {
         self.tasks
             .values()
@@ -204,6 +401,88 @@ impl TaskManager {
     }
 }
 
+async fn create_task_post(
+    ctx: &Context,
+    task: &Task,
+    forum: ChannelId,
+    name: String,
+    note: String,
+) -> Result<GuildChannel, String> {
+    let old_thread = fetch_thread(ctx, task.thread_id)?;
+    let old_forum = fetch_channel(ctx, old_thread.parent_id.ok_or("thread has no forum")?)?;
+    let new_forum = fetch_channel(ctx, forum)?;
+
+    let mut content = note;
+    match task
+        .thread_id
+        .message(&ctx.http, MessageId::new(task.thread_id.get()))
+        .await
+    {
+        Ok(starter) => {
+            content = format!("{}\n\n{}", content, starter.content);
+            for attachment in starter.attachments.iter() {
+                content = format!("{}\n{}", content, attachment.url);
+            }
+        }
+        Err(e) => {
+            Logger::debug(
+                "task.create_task_post",
+                &format!(
+                    "cannot fetch starter message of task \"{}\": {}",
+                    task.name.get(),
+                    e
+                ),
+            )
+            .await;
+        }
+    }
+
+    let tags_man = TAGSMANAGER.read().await;
+    let is_state_tag = |id: &ForumTagId| match tags_man.get(id) {
+        Some(tag) => matches!(
+            tag.tag_type,
+            Some(TageTypes::ClosedTask) | Some(TageTypes::InWork) | Some(TageTypes::NeedsInfo)
+        ),
+        None => false,
+    };
+
+    let tag_names: Vec<&String> = old_forum
+        .available_tags
+        .iter()
+        .filter(|tag| old_thread.applied_tags.contains(&tag.id) && !is_state_tag(&tag.id))
+        .map(|tag| &tag.name)
+        .collect();
+
+    let mut tags: Vec<ForumTagId> = new_forum
+        .available_tags
+        .iter()
+        .filter(|tag| tag_names.contains(&&tag.name) && !is_state_tag(&tag.id))
+        .map(|tag| tag.id)
+        .collect();
+
+    for tag in tags_man
+        .get_by_type(&forum, TageTypes::InWork)
+        .unwrap_or_default()
+    {
+        if !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    drop(tags_man);
+
+    forum
+        .create_forum_post(
+            &ctx.http,
+            CreateForumPost::new(
+                name,
+                CreateMessage::new().content(content.chars().take(2000).collect::<String>()),
+            )
+            .set_applied_tags(tags),
+        )
+        .await
+        .map_err(|e| format!("cannot create forum post, {}", e))
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct Task {
     pub id: u32,
@@ -217,9 +496,11 @@ pub struct Task {
     pub members: TaskOption<Vec<UserId>>,
     pub start_date: Option<Timestamp>,
     pub end_date: TaskOption<Option<Timestamp>>,
-    pub last_save: TaskOption<Option<String>>,
+    pub last_save: TaskOption<Option<LastSave>>,
     #[serde(default, skip_serializing)]
     pub ending_results: HashMap<UserId, f64>,
+    #[serde(default)]
+    pub close_results: HashMap<UserId, i64>,
 }
 
 impl Task {
@@ -227,7 +508,7 @@ impl Task {
         ctx: &Context,
         id: u32,
         project: String,
-        waiter_role: Option<RoleId>,
+        forum: &TaskForum,
         thread: &mut GuildChannel,
     ) -> Result<Self, String> {
         let mut instance = Self {
@@ -249,19 +530,26 @@ impl Task {
             end_date: TaskOption::new(None),
             last_save: TaskOption::new(None),
             ending_results: HashMap::new(),
+            close_results: HashMap::new(),
         };
 
-        if let Some(tags) = TAGSMANAGER
+        let mut tags = forum.default_tags.clone();
+        if let Some(in_work) = TAGSMANAGER
             .try_read()
             .map_err(|e| e.to_string())?
             .get_by_type(&thread.parent_id.unwrap(), TageTypes::InWork)
         {
+            tags.extend(in_work);
+        }
+
+        if tags.iter().any(|tag| !thread.applied_tags.contains(tag)) {
             let mut new_tags = thread.applied_tags.clone();
             for tag in tags {
                 if !new_tags.contains(&tag) {
                     new_tags.push(tag);
                 }
             }
+            new_tags.truncate(5);
 
             thread
                 .edit_thread(&ctx.http, EditThread::new().applied_tags(new_tags))
@@ -269,7 +557,7 @@ impl Task {
                 .map_err(|e| format!("cannot change thread tags, {}", e.to_string()))?
         }
 
-        if let Some(ping_msg) = instance.get_roles_ping(&thread, waiter_role) {
+        if let Some(ping_msg) = instance.get_roles_ping(&thread, forum.waiter_role) {
             thread
                 .send_message(&ctx.http, CreateMessage::new().content(ping_msg))
                 .await
@@ -340,8 +628,71 @@ impl Task {
         .await;
     }
 
+    pub async fn replace_member(&mut self, from: UserId, into: UserId) -> bool {
+        let mut found = false;
+
+        if let Some(result) = self.ending_results.remove(&from) {
+            self.ending_results.insert(into, result);
+            found = true;
+        }
+
+        if let Some(result) = self.close_results.remove(&from) {
+            *self.close_results.entry(into).or_default() += result;
+            found = true;
+        }
+
+        self.members.map_values(|members| {
+            if members.contains(&from) {
+                members.retain(|id| id != &from);
+                if !members.contains(&into) {
+                    members.push(into);
+                }
+                found = true;
+            }
+        });
+
+        self.mentor_id.map_values(|mentor| {
+            if mentor == &Some(from) {
+                *mentor = Some(into);
+                found = true;
+            }
+        });
+
+        if found {
+            self.update().await;
+        }
+
+        found
+    }
+
+    pub async fn scrub_member(&mut self, member: UserId) -> bool {
+        let mut found = self.ending_results.remove(&member).is_some();
+        found |= self.close_results.remove(&member).is_some();
+
+        self.members.map_values(|members| {
+            if members.contains(&member) {
+                members.retain(|id| id != &member);
+                found = true;
+            }
+        });
+
+        self.mentor_id.map_values(|mentor| {
+            if mentor == &Some(member) {
+                *mentor = None;
+                found = true;
+            }
+        });
+
+        if found {
+            self.update().await;
+        }
+
+        found
+    }
+
     pub async fn update(&self) {
         self.serialize().await;
+        ProjectManager::request_stat_update();
     }
 
     pub async fn close(&mut self, ctx: &Context) {
@@ -362,8 +713,9 @@ impl Task {
                 member.leave_task(&self).await;
 
                 let end_score = self.ending_results.get(member_id).unwrap_or(&1.0).round() as i64;
+                self.close_results.insert(*member_id, end_score);
 
-                member.change_score(end_score).await;
+                member.change_project_score(&self.project, end_score).await;
 
                 if end_score > 0 {
                     if &Some(member_id.clone()) != self.mentor_id.get() {
@@ -422,6 +774,8 @@ impl Task {
             }
         }
 
+        self.archive_thread(ctx);
+
         match thread
             .edit_thread(
                 &ctx.http,
@@ -451,6 +805,41 @@ impl Task {
         }
     }
 
+    fn archive_thread(&self, ctx: &Context) {
+        let http = ctx.http.clone();
+        let task_id = self.id;
+        let task_name = self.name.get().clone();
+        let thread_id = self.thread_id;
+
+        tokio::spawn(async move {
+            match TaskTranscript::collect(&http, task_id, task_name.clone(), thread_id).await {
+                Ok(transcript) => {
+                    transcript.write().await;
+
+                    Logger::debug(
+                        "task.archive_thread",
+                        &format!(
+                            "archived {} messages of task \"{}\"",
+                            transcript.messages.len(),
+                            task_name
+                        ),
+                    )
+                    .await;
+                }
+                Err(e) => {
+                    Logger::error(
+                        "task.archive_thread",
+                        &format!(
+                            "cannot collect thread history of task \"{}\": {}",
+                            task_name, e
+                        ),
+                    )
+                    .await;
+                }
+            }
+        });
+    }
+
     pub async fn open(&mut self, ctx: &Context) {
         if !self.finished {
             return;
@@ -609,7 +998,7 @@ impl Task {
         true
     }
 
-    pub async fn set_last_save(&mut self, ctx: &Context, last_save: Option<String>) {
+    pub async fn set_last_save(&mut self, ctx: &Context, last_save: Option<LastSave>) {
         self.last_save.set(last_save);
         self.update().await;
 
@@ -644,7 +1033,7 @@ impl Task {
                 .send_message(
                     &ctx.http,
                     CreateMessage::new().content(match self.last_save.get() {
-                        Some(ref save) => loc!("task-last-save", "save" = save),
+                        Some(save) => loc!("task-last-save", "save" = save),
                         None => loc!(
                             "task-last-save",
                             "save" = loc!("task-lask-save-not-specified")
@@ -851,6 +1240,22 @@ impl Task {
         Some(ping)
     }
 
+    pub fn save_candidates(&self) -> Vec<UserId> {
+        let mut candidates = Vec::new();
+
+        if let Some(mentor) = self.mentor_id.get() {
+            candidates.push(*mentor);
+        }
+
+        for member in self.members.get().iter() {
+            if !candidates.contains(member) {
+                candidates.push(*member);
+            }
+        }
+
+        candidates
+    }
+
     pub fn get_members_ping(&self) -> String {
         let mut ping = String::new();
         for member in self.members.get().iter() {
@@ -1117,7 +1522,7 @@ impl Task {
             format!(
                 "`{}`",
                 match self.last_save.get() {
-                    Some(save) => save.clone(),
+                    Some(save) => save.to_string(),
                     None => loc!("task-embed-no-last-save"),
                 }
             ),
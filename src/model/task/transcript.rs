@@ -0,0 +1,208 @@
+use crate::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json;
+use serenity::{
+    all::GetMessages,
+    http::Http,
+    model::{
+        channel::Message,
+        id::{ChannelId, MessageId, UserId},
+        timestamp::Timestamp,
+    },
+};
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TranscriptMessage {
+    pub author_id: UserId,
+    pub author_name: String,
+    pub timestamp: Timestamp,
+    pub content: String,
+    pub attachments: Vec<String>,
+}
+
+impl From<&Message> for TranscriptMessage {
+    fn from(message: &Message) -> Self {
+        Self {
+            author_id: message.author.id,
+            author_name: message.author.name.clone(),
+            timestamp: message.timestamp,
+            content: message.content.clone(),
+            attachments: message.attachments.iter().map(|x| x.url.clone()).collect(),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Clone, Debug)]
+pub struct TaskTranscript {
+    pub task_id: u32,
+    pub task_name: String,
+    pub thread_id: ChannelId,
+    pub created: Timestamp,
+    pub messages: Vec<TranscriptMessage>,
+}
+
+impl TaskTranscript {
+    pub async fn collect(
+        http: &Http,
+        task_id: u32,
+        task_name: String,
+        thread_id: ChannelId,
+    ) -> Result<Self, serenity::Error> {
+        let mut messages = Vec::new();
+        let mut before: Option<MessageId> = None;
+
+        loop {
+            let mut request = GetMessages::new().limit(100);
+            if let Some(id) = before {
+                request = request.before(id);
+            }
+
+            let page = thread_id.messages(http, request).await?;
+
+            if page.is_empty() {
+                break;
+            }
+
+            before = page.last().map(|x| x.id);
+            messages.extend(page.iter().map(TranscriptMessage::from));
+
+            if page.len() < 100 {
+                break;
+            }
+        }
+
+        messages.reverse();
+
+        Ok(Self {
+            task_id,
+            task_name,
+            thread_id,
+            created: Timestamp::now(),
+            messages,
+        })
+    }
+
+    pub fn load(task_id: u32) -> Option<Self> {
+        let content = read_file(&DATA_PATH.join(format!("archives/tasks/{}", task_id)));
+
+        if content.is_empty() {
+            return None;
+        }
+
+        serde_json::from_str(&content).ok()
+    }
+
+    pub async fn write(&self) {
+        write_file(
+            &DATA_PATH.join(format!("archives/tasks/{}", self.task_id)),
+            match serde_json::to_string(&self) {
+                Ok(content) => content,
+                Err(e) => {
+                    Logger::error(
+                        "transcript.write",
+                        &format!(
+                            "cannot serialize transcript of task {}: {}",
+                            self.task_id, e
+                        ),
+                    )
+                    .await;
+                    return;
+                }
+            },
+        );
+    }
+
+    pub fn replace_user(&mut self, from: UserId, into: UserId) -> bool {
+        let mut found = false;
+        let (mention, replacement) = (format!("<@{}>", from.get()), format!("<@{}>", into.get()));
+
+        for message in self.messages.iter_mut() {
+            if message.author_id == from {
+                message.author_id = into;
+                message.author_name = loc!("task-transcript-erased-author");
+                found = true;
+            }
+
+            if message.content.contains(&mention) {
+                message.content = message.content.replace(&mention, &replacement);
+                found = true;
+            }
+        }
+
+        found
+    }
+
+    pub fn to_markdown(&self) -> String {
+        self.render_markdown(&loc!("task-transcript-title", "task" = self.task_name))
+    }
+
+    pub fn to_html(&self) -> String {
+        self.render_html(&loc!("task-transcript-title", "task" = self.task_name))
+    }
+
+    pub fn render_markdown(&self, title: &str) -> String {
+        let mut text = format!("# {}\n\n", title);
+
+        for message in self.messages.iter() {
+            text = format!(
+                "{}**{}** ({}) `{}`\n",
+                text,
+                message.author_name,
+                message.author_id.get(),
+                message.timestamp
+            );
+
+            if !message.content.is_empty() {
+                text = format!("{}{}\n", text, message.content);
+            }
+
+            for url in message.attachments.iter() {
+                text = format!("{}- {}\n", text, url);
+            }
+
+            text.push('\n');
+        }
+
+        text
+    }
+
+    pub fn render_html(&self, title: &str) -> String {
+        let mut body = String::new();
+
+        for message in self.messages.iter() {
+            let mut attachments = String::new();
+            for url in message.attachments.iter() {
+                attachments = format!(
+                    "{}<li><a href=\"{}\">{}</a></li>",
+                    attachments,
+                    escape_html(url),
+                    escape_html(url)
+                );
+            }
+
+            body = format!(
+                "{}<div class=\"message\"><p><b>{}</b> ({}) <i>{}</i></p><p>{}</p><ul>{}</ul></div>\n",
+                body,
+                escape_html(&message.author_name),
+                message.author_id.get(),
+                message.timestamp,
+                escape_html(&message.content).replace("\n", "<br>"),
+                attachments
+            );
+        }
+
+        format!(
+            "<!DOCTYPE html>\n<html>\n<head><meta charset=\"utf-8\"><title>{}</title></head>\n<body>\n<h1>{}</h1>\n{}</body>\n</html>\n",
+            escape_html(&self.task_name),
+            escape_html(title),
+            body
+        )
+    }
+}
+
+pub fn escape_html(text: &str) -> String {
+    text.replace("&", "&amp;")
+        .replace("<", "&lt;")
+        .replace(">", "&gt;")
+        .replace("\"", "&quot;")
+}
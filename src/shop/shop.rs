@@ -126,6 +126,14 @@ impl ShopManager {
         .await;
     }
 
+    pub fn currency_references(&self, project: &str) -> Vec<String> {
+        self.pages
+            .iter()
+            .filter(|page| page.currency.as_deref() == Some(project))
+            .map(|page| page.name.clone())
+            .collect()
+    }
+
     pub fn convert_string(&self, string: String) -> Replacement {
         let mut out = Replacement::Str(loc!(&string));
 
@@ -250,6 +258,8 @@ pub struct Page {
     access: Vec<String>,
     #[serde(rename = "notAccess", default)]
     not_access: Vec<String>,
+    #[serde(default)]
+    currency: Option<String>,
     #[serde(rename = "onBuy")]
     on_buy: Vec<ShopActions>,
 }
@@ -271,8 +281,15 @@ impl Page {
         Ok(())
     }
 
+    fn balance(&self, member: &ProjectMember) -> i64 {
+        match &self.currency {
+            Some(project) => member.project_score(project),
+            None => member.total_score(),
+        }
+    }
+
     pub async fn buy(&self, inter: &ComponentInteraction, member: &mut ProjectMember) {
-        if member.score < self.price {
+        if self.balance(member) < self.price {
             return;
         }
 
@@ -339,7 +356,14 @@ impl Page {
             }
         }
 
-        member.change_score(-self.price + self.cash_back).await;
+        match &self.currency {
+            Some(project) => {
+                member
+                    .change_project_score(project, -self.price + self.cash_back)
+                    .await
+            }
+            None => member.change_score(-self.price + self.cash_back).await,
+        }
 
         Logger::low(
             "shop.page.buy",
@@ -347,7 +371,7 @@ impl Page {
                 "user {} score has been changed to {} and is now {}",
                 dis_member.display_name(),
                 -self.price + self.cash_back,
-                member.score
+                self.balance(member)
             ),
         )
         .await;
@@ -382,7 +406,10 @@ impl Page {
                 false,
             )
             .field(
-                loc!("shop-embed-price"),
+                match &self.currency {
+                    Some(project) => loc!("shop-embed-project-price", "project" = project),
+                    None => loc!("shop-embed-price"),
+                },
                 format!("```{}```", self.price),
                 true,
             )
@@ -393,7 +420,7 @@ impl Page {
             )
             .field(
                 loc!("shop-embed-balance"),
-                format!("```{}```", member.score),
+                format!("```{}```", self.balance(member)),
                 true,
             )
     }
@@ -0,0 +1,244 @@
+use png::{BitDepth, ColorType, Encoder};
+use std::f64::consts::PI;
+
+pub const CHART_WIDTH: u32 = 800;
+pub const CHART_HEIGHT: u32 = 300;
+pub const PIE_SIZE: u32 = 300;
+
+pub const CHART_PALETTE: [([u8; 3], char); 8] = [
+    ([0x55, 0xac, 0xee], '🟦'),
+    ([0x78, 0xb1, 0x59], '🟩'),
+    ([0xfd, 0xcb, 0x58], '🟨'),
+    ([0xf4, 0x90, 0x0c], '🟧'),
+    ([0xdd, 0x2e, 0x44], '🟥'),
+    ([0xaa, 0x8e, 0xd6], '🟪'),
+    ([0xc1, 0x69, 0x4f], '🟫'),
+    ([0xe6, 0xe7, 0xe8], '⬜'),
+];
+
+const BACKGROUND: [u8; 3] = [0x2b, 0x2d, 0x31];
+const GRID: [u8; 3] = [0x3f, 0x41, 0x47];
+const AXIS: [u8; 3] = [0x80, 0x84, 0x8e];
+const MARGIN: i64 = 20;
+const GRID_LINES: i64 = 4;
+
+pub fn palette_color(index: usize) -> [u8; 3] {
+    CHART_PALETTE[index % CHART_PALETTE.len()].0
+}
+
+pub fn palette_mark(index: usize) -> char {
+    CHART_PALETTE[index % CHART_PALETTE.len()].1
+}
+
+pub struct Canvas {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            pixels: BACKGROUND.repeat((width * height) as usize),
+        }
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 3] {
+        let index = ((y * self.width + x) * 3) as usize;
+        [
+            self.pixels[index],
+            self.pixels[index + 1],
+            self.pixels[index + 2],
+        ]
+    }
+
+    fn put(&mut self, x: i64, y: i64, color: [u8; 3]) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+
+        let index = ((y as u32 * self.width + x as u32) * 3) as usize;
+        self.pixels[index..index + 3].copy_from_slice(&color);
+    }
+
+    pub fn fill_rect(&mut self, x: i64, y: i64, width: i64, height: i64, color: [u8; 3]) {
+        for py in y..y + height {
+            for px in x..x + width {
+                self.put(px, py, color);
+            }
+        }
+    }
+
+    pub fn line(&mut self, from: (i64, i64), to: (i64, i64), width: i64, color: [u8; 3]) {
+        let (mut x, mut y) = from;
+        let dx = (to.0 - x).abs();
+        let dy = -(to.1 - y).abs();
+        let sx = if x < to.0 { 1 } else { -1 };
+        let sy = if y < to.1 { 1 } else { -1 };
+        let mut error = dx + dy;
+        let offset = width / 2;
+
+        loop {
+            self.fill_rect(x - offset, y - offset, width, width, color);
+
+            if x == to.0 && y == to.1 {
+                break;
+            }
+
+            let double = 2 * error;
+            if double >= dy {
+                error += dy;
+                x += sx;
+            }
+            if double <= dx {
+                error += dx;
+                y += sy;
+            }
+        }
+    }
+
+    pub fn encode(&self) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+
+        let mut encoder = Encoder::new(&mut data, self.width, self.height);
+        encoder.set_color(ColorType::Rgb);
+        encoder.set_depth(BitDepth::Eight);
+
+        let mut writer = encoder.write_header().map_err(|e| e.to_string())?;
+        writer
+            .write_image_data(&self.pixels)
+            .map_err(|e| e.to_string())?;
+        writer.finish().map_err(|e| e.to_string())?;
+
+        Ok(data)
+    }
+}
+
+fn plot_grid(canvas: &mut Canvas) -> (i64, i64, i64, i64) {
+    let (left, top) = (MARGIN, MARGIN);
+    let (right, bottom) = (canvas.width as i64 - MARGIN, canvas.height as i64 - MARGIN);
+
+    for line in 0..GRID_LINES {
+        let y = top + (bottom - top) * line / GRID_LINES;
+        canvas.line((left, y), (right, y), 1, GRID);
+    }
+    canvas.line((left, bottom), (right, bottom), 2, AXIS);
+    canvas.line((left, top), (left, bottom), 2, AXIS);
+
+    (left, top, right, bottom)
+}
+
+pub fn bucket_by_period(times: &[i64], now: i64, period: i64, count: usize) -> Vec<i64> {
+    let mut buckets = vec![0; count];
+    let start = now - period * count as i64;
+
+    for time in times.iter() {
+        if *time < start || *time >= now {
+            continue;
+        }
+        buckets[((time - start) / period) as usize] += 1;
+    }
+
+    buckets
+}
+
+pub fn bar_chart(values: &[i64]) -> Result<Vec<u8>, String> {
+    let mut canvas = Canvas::new(CHART_WIDTH, CHART_HEIGHT);
+    let (left, top, right, bottom) = plot_grid(&mut canvas);
+
+    let max = values.iter().cloned().max().unwrap_or(0).max(1);
+    let slot = (right - left) / values.len().max(1) as i64;
+    let width = (slot * 3 / 4).max(1);
+
+    for (index, value) in values.iter().enumerate() {
+        let height = (bottom - top) * value.max(&0) / max;
+        canvas.fill_rect(
+            left + slot * index as i64 + (slot - width) / 2,
+            bottom - height,
+            width,
+            height,
+            palette_color(0),
+        );
+    }
+
+    canvas.encode()
+}
+
+pub fn line_chart(points: &[(i64, i64)]) -> Result<Vec<u8>, String> {
+    let mut canvas = Canvas::new(CHART_WIDTH, CHART_HEIGHT);
+    let (left, top, right, bottom) = plot_grid(&mut canvas);
+
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return canvas.encode();
+    };
+
+    let min_y = points.iter().map(|x| x.1).min().unwrap_or(0).min(0);
+    let max_y = points.iter().map(|x| x.1).max().unwrap_or(0).max(min_y + 1);
+    let span_x = (last.0 - first.0).max(1);
+
+    let project = |(x, y): &(i64, i64)| {
+        (
+            left + (right - left) * (x - first.0) / span_x,
+            bottom - (bottom - top) * (y - min_y) / (max_y - min_y),
+        )
+    };
+
+    if min_y < 0 {
+        let zero = project(&(first.0, 0)).1;
+        canvas.line((left, zero), (right, zero), 1, AXIS);
+    }
+
+    for pair in points.windows(2) {
+        canvas.line(project(&pair[0]), project(&pair[1]), 3, palette_color(1));
+    }
+
+    for point in points.iter() {
+        let (x, y) = project(point);
+        canvas.fill_rect(x - 3, y - 3, 7, 7, palette_color(1));
+    }
+
+    canvas.encode()
+}
+
+pub fn pie_chart(values: &[u64]) -> Result<Vec<u8>, String> {
+    let mut canvas = Canvas::new(PIE_SIZE, PIE_SIZE);
+    let total: u64 = values.iter().sum();
+
+    if total == 0 {
+        return canvas.encode();
+    }
+
+    let center = PIE_SIZE as f64 / 2.0;
+    let outer = center - MARGIN as f64;
+    let inner = outer / 2.0;
+
+    let mut bounds = Vec::new();
+    let mut sum = 0;
+    for value in values.iter() {
+        sum += value;
+        bounds.push(sum as f64 / total as f64);
+    }
+
+    for y in 0..PIE_SIZE {
+        for x in 0..PIE_SIZE {
+            let (dx, dy) = (x as f64 + 0.5 - center, y as f64 + 0.5 - center);
+            let distance = (dx * dx + dy * dy).sqrt();
+
+            if distance > outer || distance < inner {
+                continue;
+            }
+
+            let angle = (dx.atan2(-dy) + 2.0 * PI) % (2.0 * PI) / (2.0 * PI);
+            let index = bounds
+                .iter()
+                .position(|bound| angle < *bound)
+                .unwrap_or(values.len() - 1);
+
+            canvas.put(x as i64, y as i64, palette_color(index));
+        }
+    }
+
+    canvas.encode()
+}
@@ -1,3 +1,5 @@
+mod chart;
 mod utils;
 
+pub use chart::*;
 pub use utils::*;
@@ -40,6 +40,46 @@ pub async fn fetch_member(id: &UserId) -> Result<Member, serenity::Error> {
     guild.member(http, id).await
 }
 
+pub fn split_pages<T>(
+    items: Vec<T>,
+    max_items: usize,
+    max_size: usize,
+    size: impl Fn(&T) -> usize,
+) -> Vec<Vec<T>> {
+    let mut pages: Vec<Vec<T>> = Vec::from([Vec::new()]);
+    let mut total = 0;
+
+    for item in items {
+        let length = size(&item);
+        let page = pages.last_mut().unwrap();
+
+        if !page.is_empty() && (page.len() >= max_items || total + length > max_size) {
+            pages.push(Vec::new());
+            total = 0;
+        }
+
+        pages.last_mut().unwrap().push(item);
+        total += length;
+    }
+
+    pages
+}
+
+pub async fn fetch_members(http: &Http) -> Result<Vec<Member>, serenity::Error> {
+    let mut members: Vec<Member> = Vec::new();
+
+    loop {
+        let after = members.last().map(|x| x.user.id);
+        let page = get_guild().members(http, Some(1000), after).await?;
+        let done = page.len() < 1000;
+
+        members.extend(page);
+        if done {
+            return Ok(members);
+        }
+    }
+}
+
 pub fn fetch_channel(ctx: &Context, id: ChannelId) -> Result<GuildChannel, String> {
     let guild = match get_guild().to_guild_cached(&ctx.cache) {
         Some(g) => g,
@@ -89,6 +129,56 @@ pub fn get_params_buttons(name: &str, params: Vec<&str>) -> Vec<CreateActionRow>
     buttons
 }
 
+pub fn get_compact_params_buttons(name: &str, params: Vec<&str>) -> Vec<CreateActionRow> {
+    let mut rows = Vec::new();
+    for chunk in params.chunks(2) {
+        let mut buttons = Vec::new();
+        for param in chunk.iter() {
+            buttons.push(
+                CreateButton::new(format!("{}:{}-label", name, param))
+                    .label(loc!(&format!("{}-{}-label", name, param)))
+                    .style(ButtonStyle::Secondary)
+                    .disabled(true),
+            );
+            buttons.push(
+                CreateButton::new(format!("{}:{}", name, param))
+                    .emoji('🛠')
+                    .style(ButtonStyle::Success),
+            );
+        }
+        rows.push(CreateActionRow::Buttons(buttons));
+    }
+    rows
+}
+
+pub fn box_list(lines: Vec<String>) -> String {
+    let mut text = String::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        text = format!(
+            "{}{} {}\n",
+            text,
+            match index + 1 == lines.len() {
+                false => "╠︎",
+                true => "╚",
+            },
+            line
+        );
+    }
+
+    text
+}
+
+pub fn truncate_text(text: String, max: usize) -> String {
+    if text.chars().count() <= max {
+        return text;
+    }
+
+    let mut text: String = text.chars().take(max - 1).collect();
+    text.push('…');
+    text
+}
+
 pub async fn get_highest_role_in(
     ctx: &Context,
     user: UserId,
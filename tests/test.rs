@@ -5,18 +5,32 @@ use serenity::{
         application::CommandInteraction,
         channel::{Attachment, PartialChannel},
         guild::Role,
-        id::GuildId,
+        id::{GuildId, MessageId, UserId},
+        timestamp::Timestamp,
         user::User,
     },
 };
-use std::fs;
+use std::{collections::HashMap, fs};
 use task_bot::{
     command_manager::*,
     config::*,
     connect::*,
     localization::*,
     logger::*,
-    model::{member::MEMBERSMANAGER, project::PROJECTMANAGER, tag::TAGSMANAGER, task::TASKMANAGER},
+    model::{
+        achievement::ActivityStreak,
+        member::{
+            attribute_score, merge_score_history, normalize_ckey, paginate_fields, NotesHistory,
+            OnboardingConfig, ProjectMember, ScoreTransfer, TransferError, TransferPolicy,
+            TransferState, WarnPolicy, MEMBERSMANAGER,
+        },
+        project::{
+            split_stat_fields, Caller, DigestStats, InactivityDecision, InactivityPolicy, Project,
+            ProjectPermission, StatGroup, StatSort, StatTemplate, PROJECTMANAGER,
+        },
+        tag::TAGSMANAGER,
+        task::{escape_html, Task, TaskOption, TaskTranscript, TranscriptMessage, TASKMANAGER},
+    },
     prelude::*,
 };
 use tokio;
@@ -216,12 +230,38 @@ fn logger_test() {
 async fn connect_test() {
     assert_eq!(
         get_user_id("dfhshfehwifhewhj2h1h2jbfnewbjehfjdhskjkhejhfdkjsh".to_string()).await,
-        "Not Found".to_string()
+        None
     );
-    assert_ne!(
-        get_user_id("M0ta".to_string()).await,
-        "Not Found".to_string()
-    )
+    assert!(get_user_id("M0ta".to_string()).await.is_some())
+}
+
+#[test]
+fn normalize_ckey_test() {
+    assert_eq!(normalize_ckey("M0ta"), "m0ta");
+    assert_eq!(normalize_ckey(" Some_User-1 "), "someuser1");
+    assert_eq!(normalize_ckey("Some_User"), "someuser");
+}
+
+#[test]
+fn file_date_test() {
+    assert!(parse_file_date("18-Oct-2026 12:34").is_some());
+    assert!(parse_file_date("  01-Jan-2024 00:00 ").is_some());
+    assert_eq!(parse_file_date("-"), None);
+
+    let older = SaveFile {
+        db: "test".to_string(),
+        folder: "folder".to_string(),
+        file: "old.yml".to_string(),
+        date: "01-Jan-2024 00:00".to_string(),
+    };
+    let newer = SaveFile {
+        file: "sub/new.yml".to_string(),
+        date: "18-Oct-2026 12:34".to_string(),
+        ..older.clone()
+    };
+
+    assert!(older.timestamp() < newer.timestamp());
+    assert_eq!(newer.path(), "folder/sub/new.yml".to_string());
 }
 
 #[tokio::test]
@@ -302,10 +342,669 @@ async fn projects_manager_test() {
     let mut proj_man = PROJECTMANAGER.try_write().unwrap();
     proj_man.init().await;
 
-    fs::remove_file(DATA_PATH.join("databases/projects/test_project.yml"))
+    let id = proj_man.get(&"test project".to_string()).unwrap().id;
+    assert!(id > 0);
+    assert!(!fs::exists(DATA_PATH.join("databases/projects/test_project.yml")).unwrap());
+
+    fs::remove_file(DATA_PATH.join(format!("databases/projects/{}", id)))
         .expect("Cannot delete test project file");
 }
 
+#[test]
+fn required_sections_test() {
+    let project = Project {
+        id: 1,
+        name: "test project".to_string(),
+        max_tasks_per_user: 2,
+        tasks_forum: 4321324324.into(),
+        waiter_role: None,
+        forums: Vec::new(),
+        stat_posts: Default::default(),
+        stat_channel: None,
+        associated_roles: Vec::new(),
+        inactivity: None,
+        archive: None,
+        managers: Vec::new(),
+        digest: None,
+        required_sections: vec![
+            "Описание".to_string(),
+            "Ссылки".to_string(),
+            "Сроки".to_string(),
+        ],
+    };
+
+    assert_eq!(
+        project.missing_sections("## Описание\nтекст\n**Ссылки:** нет"),
+        vec!["Сроки".to_string()]
+    );
+    assert!(project
+        .missing_sections("# описание:\n> ссылки\n__Сроки__")
+        .is_empty());
+    assert_eq!(project.missing_sections("").len(), 3);
+}
+
+#[test]
+fn warn_expiry_test() {
+    let policy = WarnPolicy {
+        expire_days: Some(30),
+        appeal_channel: None,
+        thresholds: Vec::new(),
+    };
+    let now = Timestamp::now().unix_timestamp();
+    let warn = |days: i64| {
+        NotesHistory::Current((
+            1.into(),
+            Timestamp::from_unix_timestamp(now - days * 86400).unwrap(),
+            "test".to_string(),
+        ))
+    };
+
+    assert!(policy.is_active(&warn(1)));
+    assert!(!policy.is_active(&warn(31)));
+    assert!(policy.is_active(&NotesHistory::OldFormat("old".to_string())));
+    assert!(WarnPolicy::default().is_active(&warn(365)));
+}
+
+#[test]
+fn score_transfer_limits_test() {
+    let policy = TransferPolicy {
+        daily_amount: Some(100),
+        daily_transfers: Some(3),
+        min_account_days: Some(30),
+        allowed_roles: vec![5.into()],
+        approval_threshold: None,
+        approval_channel: None,
+    };
+    let now = Timestamp::now().unix_timestamp();
+    let transfer = |amount: i64, hours: i64, state: TransferState| ScoreTransfer {
+        from: 1.into(),
+        to: 2.into(),
+        amount,
+        reason: "test".to_string(),
+        time: Timestamp::from_unix_timestamp(now - hours * 3600).unwrap(),
+        message: None,
+        state,
+    };
+
+    let history = vec![
+        transfer(40, 1, TransferState::Done),
+        transfer(50, 2, TransferState::Denied(3.into())),
+        transfer(80, 30, TransferState::Done),
+    ];
+    assert!(policy.check_limits(&history, 1.into(), 60, now).is_ok());
+    assert_eq!(
+        policy.check_limits(&history, 1.into(), 61, now),
+        Err(TransferError::DailyAmount(60))
+    );
+
+    let history = vec![transfer(1, 1, TransferState::Pending); 3];
+    assert_eq!(
+        policy.check_limits(&history, 1.into(), 1, now),
+        Err(TransferError::DailyTransfers(3))
+    );
+
+    let created = |days: i64| Timestamp::from_unix_timestamp(now - days * 86400).unwrap();
+    assert!(policy.is_eligible(&[], created(31), now));
+    assert!(!policy.is_eligible(&[], created(1), now));
+    assert!(policy.is_eligible(&[5.into()], created(1), now));
+    assert!(TransferPolicy::default().is_eligible(&[], created(0), now));
+}
+
+#[test]
+fn onboarding_config_test() {
+    let config: OnboardingConfig = toml::from_str(
+        r#"
+        review_channel = 1
+        folder_question = "folder"
+
+        [[questions]]
+        id = "about"
+        label = "onboarding-question-about"
+        paragraph = true
+
+        [[questions]]
+        id = "folder"
+        label = "onboarding-question-folder"
+        required = false
+        "#,
+    )
+    .unwrap();
+
+    assert!(config.is_enabled());
+    assert!(config.questions[0].required);
+    assert!(!config.questions[1].required);
+    assert!(!OnboardingConfig::default().is_enabled());
+
+    let questions = (0..6)
+        .map(|i| format!("[[questions]]\nid = \"q{}\"\nlabel = \"q{}\"\n", i, i))
+        .collect::<String>();
+    assert!(toml::from_str::<OnboardingConfig>(&questions).is_err());
+}
+
+#[test]
+fn profile_pagination_test() {
+    let lines: Vec<String> = (0..300).map(|i| format!("{:0>40}", i)).collect();
+    let pages = paginate_fields(
+        vec![
+            ("tasks".to_string(), lines),
+            ("long".to_string(), vec!["x".repeat(3000)]),
+        ],
+        6,
+    );
+
+    assert!(pages.len() > 1);
+    for page in pages.iter() {
+        assert!(page.len() <= 6);
+        assert!(
+            page.iter()
+                .map(|(name, value)| name.chars().count() + value.chars().count())
+                .sum::<usize>()
+                <= 6000
+        );
+        assert!(page.iter().all(|(_, value)| value.chars().count() <= 1024));
+    }
+    assert_eq!(paginate_fields(Vec::new(), 6), vec![Vec::new()]);
+}
+
+#[test]
+fn stat_template_test() {
+    let template: StatTemplate = serde_yaml::from_str(
+        r#"
+        title: "{name} ({score})"
+        body: "done: {done}, last: {last_activity}"
+        inline: false
+        sort: activity
+        group: eachRole
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(template.sort, StatSort::Activity);
+    assert_eq!(template.group, StatGroup::EachRole);
+
+    let values = HashMap::from([
+        ("name", "Member".to_string()),
+        ("score", "15".to_string()),
+        ("done", "3".to_string()),
+    ]);
+    let (name, value, inline) = template.field(&values);
+
+    assert_eq!(name, "Member (15)");
+    assert_eq!(value, "done: 3, last: {last_activity}");
+    assert!(!inline);
+}
+
+#[test]
+fn stat_post_split_test() {
+    let fields: Vec<(String, String, bool)> = (0..60)
+        .map(|i| (format!("member {}", i), "score".to_string(), true))
+        .collect();
+    let posts = split_stat_fields(fields);
+    assert_eq!(
+        posts.iter().map(|x| x.len()).collect::<Vec<_>>(),
+        [25, 25, 10]
+    );
+
+    let fields = vec![("name".to_string(), "x".repeat(1000), true); 12];
+    assert!(split_stat_fields(fields).len() > 1);
+
+    let project: Project = serde_yaml::from_str(
+        r#"
+name: test project
+max_tasks_per_user: 2
+tasks_forum: 4321324324
+waiter_role: null
+stat_posts:
+  847395357483: 1111
+  354375984739: [2222, 3333]
+stat_channel: null
+associated_roles: []
+"#,
+    )
+    .unwrap();
+    assert_eq!(
+        project.stat_posts[&847395357483.into()],
+        [MessageId::new(1111)]
+    );
+    assert_eq!(project.stat_posts[&354375984739.into()].len(), 2);
+}
+
+#[test]
+fn score_attribution_test() {
+    let weights = HashMap::from([
+        ("Corvax".to_string(), 2),
+        ("Wiki".to_string(), 1),
+        ("Empty".to_string(), 0),
+    ]);
+
+    let scores = attribute_score(100, &weights);
+    assert_eq!(scores["Corvax"], 67);
+    assert_eq!(scores["Wiki"], 33);
+    assert!(!scores.contains_key("Empty"));
+    assert_eq!(scores.values().sum::<i64>(), 100);
+
+    assert!(attribute_score(-5, &weights).is_empty());
+    assert!(attribute_score(10, &HashMap::new()).is_empty());
+}
+
+#[test]
+fn project_forums_test() {
+    let project: Project = serde_yaml::from_str(
+        r#"
+name: test project
+max_tasks_per_user: 2
+tasks_forum: 100
+waiter_role: 10
+stat_posts: {}
+stat_channel: null
+associated_roles: []
+forums:
+  - channel: 200
+    default_tags: [1, 2]
+  - channel: 300
+    waiter_role: 30
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(project.forum_ids().len(), 3);
+    assert!(project.has_forum(&200.into()));
+    assert!(!project.has_forum(&400.into()));
+    assert_eq!(project.forum(200.into()).waiter_role, Some(10.into()));
+    assert_eq!(project.forum(200.into()).default_tags.len(), 2);
+    assert_eq!(project.forum(300.into()).waiter_role, Some(30.into()));
+    assert!(project.forum(100.into()).default_tags.is_empty());
+}
+
+#[test]
+fn project_managers_test() {
+    let mut project: Project = serde_yaml::from_str(
+        r#"
+name: test project
+max_tasks_per_user: 2
+tasks_forum: 100
+waiter_role: null
+stat_posts: {}
+stat_channel: null
+associated_roles: []
+managers:
+  - role: 10
+    permissions: [taskChange, tagChange]
+"#,
+    )
+    .unwrap();
+
+    let caller = |roles: Vec<u64>, admin: bool| Caller {
+        id: 1.into(),
+        roles: roles.into_iter().map(|x| x.into()).collect(),
+        admin,
+    };
+
+    assert!(project.allows(
+        &caller(vec![10], false),
+        ProjectPermission::TaskChange,
+        true
+    ));
+    assert!(!project.allows(&caller(vec![10], false), ProjectPermission::TaskClose, true));
+    assert!(!project.allows(
+        &caller(vec![20], false),
+        ProjectPermission::TaskChange,
+        false
+    ));
+    assert!(project.allows(
+        &caller(vec![], true),
+        ProjectPermission::ProjectConfig,
+        false
+    ));
+
+    project.managers.clear();
+    assert!(project.allows(
+        &caller(vec![20], false),
+        ProjectPermission::MemberChange,
+        false
+    ));
+    assert!(!project.allows(
+        &caller(vec![20], false),
+        ProjectPermission::MemberChange,
+        true
+    ));
+}
+
+#[test]
+fn project_digest_test() {
+    let day = |day: i64| Timestamp::from_unix_timestamp(day * 86400).unwrap();
+    let task = |id: u32, start: i64, end: Option<i64>, results: Vec<(u64, i64)>| Task {
+        id,
+        project: "test project".to_string(),
+        thread_id: (id as u64 + 100).into(),
+        finished: end.is_some(),
+        name: TaskOption::new(format!("task {}", id)),
+        score: TaskOption::new(0),
+        max_members: TaskOption::new(10),
+        mentor_id: TaskOption::new(None),
+        members: TaskOption::new(Vec::new()),
+        start_date: Some(day(start)),
+        end_date: TaskOption::new(end.map(day)),
+        last_save: TaskOption::new(None),
+        ending_results: HashMap::new(),
+        close_results: results.into_iter().map(|(id, x)| (id.into(), x)).collect(),
+    };
+
+    let tasks: Vec<Task> = [
+        task(1, 10, Some(12), vec![(1, 1), (3, 2)]),
+        task(2, 1, Some(11), vec![(1, 1), (3, 5), (2, 0)]),
+        task(3, 12, None, Vec::new()),
+        task(4, 2, None, Vec::new()),
+        task(5, 3, Some(5), vec![(2, 10)]),
+    ]
+    .iter()
+    .map(|task| serde_json::from_str(&serde_json::to_string(task).unwrap()).unwrap())
+    .collect();
+    let stats = DigestStats::collect(
+        &tasks.iter().collect::<Vec<&Task>>(),
+        day(10).unix_timestamp(),
+        day(17).unix_timestamp(),
+        14,
+    );
+
+    assert_eq!(stats.opened, 2);
+    assert_eq!(stats.closed, 2);
+    assert_eq!(stats.average_close, Some(6 * 86400));
+    assert_eq!(stats.stale, vec![(104.into(), 15)]);
+    assert_eq!(stats.top, vec![(3.into(), 7), (1.into(), 2)]);
+}
+
+#[test]
+fn inactivity_decision_test() {
+    let day = |day: i64| day * 86400;
+    let policy = InactivityPolicy {
+        days: 30,
+        grace_days: 7,
+        downgrade_role: None,
+        enabled: Timestamp::from_unix_timestamp(day(100)).unwrap(),
+    };
+
+    assert_eq!(
+        policy.decide(day(110), None, Some(day(1)), None),
+        InactivityDecision::Keep
+    );
+    assert_eq!(
+        policy.decide(day(131), Some(day(50)), Some(day(1)), None),
+        InactivityDecision::Warn
+    );
+    assert_eq!(
+        policy.decide(day(131), Some(day(120)), None, None),
+        InactivityDecision::Keep
+    );
+    assert_eq!(
+        policy.decide(day(140), None, Some(day(125)), None),
+        InactivityDecision::Keep
+    );
+    assert_eq!(
+        policy.decide(day(135), Some(day(120)), None, Some(day(131))),
+        InactivityDecision::ClearWarning
+    );
+    assert_eq!(
+        policy.decide(day(136), None, None, Some(day(131))),
+        InactivityDecision::Keep
+    );
+    assert_eq!(
+        policy.decide(day(138), None, None, Some(day(131))),
+        InactivityDecision::Remove
+    );
+}
+
+#[test]
+fn chart_render_test() {
+    let week = 7 * 86400;
+    let buckets = bucket_by_period(&[0, 10, week + 1, 3 * week, -1], 3 * week, week, 3);
+    assert_eq!(buckets, vec![2, 1, 0]);
+
+    let mut canvas = Canvas::new(10, 10);
+    canvas.line((0, 0), (9, 9), 1, palette_color(2));
+    assert_eq!(canvas.pixel(5, 5), palette_color(2));
+    assert_ne!(canvas.pixel(9, 0), palette_color(2));
+
+    for chart in [
+        bar_chart(&buckets).unwrap(),
+        line_chart(&[(0, 5), (10, -3), (20, 12)]).unwrap(),
+        pie_chart(&[3, 1]).unwrap(),
+    ] {
+        let decoder = png::Decoder::new(chart.as_slice());
+        let reader = decoder.read_info().unwrap();
+        assert!(reader.info().width > 0);
+    }
+}
+
+#[test]
+fn task_transcript_test() {
+    assert_eq!(
+        escape_html("<a href=\"x\">&</a>"),
+        "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+    );
+
+    let transcript = TaskTranscript {
+        task_id: 1,
+        task_name: "test task".to_string(),
+        thread_id: 100.into(),
+        created: Timestamp::from_unix_timestamp(0).unwrap(),
+        messages: vec![
+            TranscriptMessage {
+                author_id: 10.into(),
+                author_name: "first".to_string(),
+                timestamp: Timestamp::from_unix_timestamp(60).unwrap(),
+                content: "hello\n<b>world</b>".to_string(),
+                attachments: vec!["https://example.com/file.png".to_string()],
+            },
+            TranscriptMessage {
+                author_id: 20.into(),
+                author_name: "second".to_string(),
+                timestamp: Timestamp::from_unix_timestamp(120).unwrap(),
+                content: String::new(),
+                attachments: Vec::new(),
+            },
+        ],
+    };
+
+    let markdown = transcript.render_markdown("title");
+    assert!(markdown.starts_with("# title\n\n"));
+    assert!(markdown.contains("**first** (10)"));
+    assert!(markdown.contains("hello\n<b>world</b>\n"));
+    assert!(markdown.contains("- https://example.com/file.png\n"));
+    assert!(markdown.find("**first**").unwrap() < markdown.find("**second**").unwrap());
+
+    let html = transcript.render_html("<title>");
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<h1>&lt;title&gt;</h1>"));
+    assert!(html.contains("<title>test task</title>"));
+    assert!(html.contains("<b>first</b> (10)"));
+    assert!(html.contains("hello<br>&lt;b&gt;world&lt;/b&gt;"));
+    assert!(html.contains("<li><a href=\"https://example.com/file.png\">"));
+    assert!(!html.contains("<b>world</b>"));
+}
+
+#[test]
+fn member_absorb_test() {
+    let member = |json: &str| serde_json::from_str::<ProjectMember>(json).unwrap();
+
+    let mut into = member(
+        r#"
+        {
+            "id": 1,
+            "own_folder": { "db": "kept", "other": null },
+            "score": 5,
+            "all_time_score": 20,
+            "project_scores": { "alpha": 3 },
+            "in_tasks": { "alpha": [1, 2] },
+            "done_tasks": { "alpha": [{ "OldFormat": "7" }] },
+            "last_activity": { "alpha": "2024-01-05T00:00:00Z" },
+            "achievements": { "first": "2024-01-10T00:00:00Z" },
+            "notes": [{ "OldFormat": "into note" }]
+        }"#,
+    );
+    let from = member(
+        r#"
+        {
+            "id": 2,
+            "own_folder": { "db": "dropped", "other": "taken" },
+            "score": 2,
+            "all_time_score": 4,
+            "project_scores": { "alpha": 1, "beta": 6 },
+            "in_tasks": { "alpha": [2, 3] },
+            "done_tasks": { "alpha": [{ "OldFormat": "8" }], "beta": [{ "OldFormat": "9" }] },
+            "last_activity": { "alpha": "2024-01-07T00:00:00Z", "beta": "2024-01-01T00:00:00Z" },
+            "achievements": { "first": "2024-01-02T00:00:00Z", "second": "2024-01-03T00:00:00Z" },
+            "ckey": {
+                "ckey": "someuser",
+                "verified": false,
+                "verified_by": null,
+                "linked": "2024-01-01T00:00:00Z"
+            },
+            "notes": [{ "OldFormat": "from note" }]
+        }"#,
+    );
+
+    into.absorb(from);
+
+    assert_eq!(into.id, 1);
+    assert_eq!(into.score, 7);
+    assert_eq!(into.all_time_score, 24);
+    assert_eq!(into.total_score(), 17);
+    assert_eq!(into.project_score(&"alpha".to_string()), 4);
+    assert_eq!(into.project_score(&"beta".to_string()), 6);
+    assert_eq!(into.in_tasks["alpha"], vec![1, 2, 3]);
+    assert_eq!(into.done_tasks["alpha"].len(), 2);
+    assert_eq!(into.done_tasks["beta"].len(), 1);
+    assert_eq!(into.own_folder["db"], Some("kept".to_string()));
+    assert_eq!(into.own_folder["other"], Some("taken".to_string()));
+    assert_eq!(
+        into.last_activity["alpha"],
+        Timestamp::parse("2024-01-07T00:00:00Z").unwrap()
+    );
+    assert_eq!(
+        into.achievements["first"],
+        Timestamp::parse("2024-01-02T00:00:00Z").unwrap()
+    );
+    assert!(into.achievements.contains_key("second"));
+    assert_eq!(into.ckey.as_ref().unwrap().ckey, "someuser");
+    assert_eq!(into.notes.len(), 2);
+    assert_eq!(into.score_history.last().unwrap().1, 17);
+}
+
+#[test]
+fn score_history_test() {
+    let day = |day: i64| Timestamp::from_unix_timestamp(day * 86400).unwrap();
+
+    let mut member: ProjectMember = serde_json::from_str(
+        r#"
+        {
+            "id": 1,
+            "own_folder": {},
+            "score": 7,
+            "done_tasks": {
+                "test project": [
+                    { "Current": { "2024-01-01T10:00:00Z": 1 } },
+                    { "Current": { "2024-01-01T12:00:00Z": 2 } },
+                    { "Current": { "2024-01-03T10:00:00Z": 3 } }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert!(member.backfill_score_history(|id, _| id as i64));
+    assert_eq!(
+        member
+            .score_history
+            .iter()
+            .map(|x| x.1)
+            .collect::<Vec<i64>>(),
+        vec![3, 6, 7]
+    );
+    assert!(!member.backfill_score_history(|_, _| 1));
+
+    let task = |id: u32, results: Vec<(u64, i64)>| Task {
+        id,
+        project: "test project".to_string(),
+        thread_id: (id as u64 + 100).into(),
+        finished: true,
+        name: TaskOption::new(format!("task {}", id)),
+        score: TaskOption::new(0),
+        max_members: TaskOption::new(10),
+        mentor_id: TaskOption::new(None),
+        members: TaskOption::new(Vec::new()),
+        start_date: Some(day(1)),
+        end_date: TaskOption::new(Some(day(2))),
+        last_save: TaskOption::new(None),
+        ending_results: HashMap::new(),
+        close_results: results.into_iter().map(|(id, x)| (id.into(), x)).collect(),
+    };
+    let tasks: Vec<Task> = [
+        task(1, vec![(2, 4)]),
+        task(2, vec![(2, 0)]),
+        task(3, vec![(2, 5)]),
+    ]
+    .iter()
+    .map(|task| serde_json::from_str(&serde_json::to_string(task).unwrap()).unwrap())
+    .collect();
+
+    let mut member: ProjectMember = serde_json::from_str(
+        r#"
+        {
+            "id": 2,
+            "own_folder": {},
+            "project_scores": { "test project": 11 },
+            "done_tasks": {
+                "test project": [
+                    { "Current": { "2024-01-01T10:00:00Z": 1 } },
+                    { "Current": { "2024-01-02T10:00:00Z": 2 } }
+                ]
+            },
+            "mentor_tasks": {
+                "test project": [
+                    { "Current": { "2024-01-03T10:00:00Z": 3 } },
+                    { "Current": { "2024-01-04T10:00:00Z": 4 } }
+                ]
+            }
+        }"#,
+    )
+    .unwrap();
+
+    assert!(member.backfill_score_history(|id, mentor| {
+        tasks
+            .iter()
+            .find(|task| task.id == id)
+            .and_then(|task| task.close_results.get(&UserId::new(2)).cloned())
+            .unwrap_or(if mentor { 2 } else { 1 })
+    }));
+    assert_eq!(
+        member
+            .score_history
+            .iter()
+            .map(|x| x.1)
+            .collect::<Vec<i64>>(),
+        vec![4, 4, 9, 11, 11]
+    );
+
+    let merged = merge_score_history(&[(day(1), 5), (day(3), 8)], &[(day(2), 2)]);
+    assert_eq!(merged, vec![(day(1), 5), (day(2), 7), (day(3), 10)]);
+}
+
+#[test]
+fn activity_streak_test() {
+    let day = |day: i64| Timestamp::from_unix_timestamp(day * 86400 + 3600).unwrap();
+    let mut streak = None;
+
+    ActivityStreak::touch(&mut streak, day(10));
+    ActivityStreak::touch(&mut streak, day(10));
+    ActivityStreak::touch(&mut streak, day(11));
+    ActivityStreak::touch(&mut streak, day(12));
+    assert_eq!(streak.as_ref().unwrap().days(), 3);
+
+    ActivityStreak::touch(&mut streak, day(14));
+    assert_eq!(streak.as_ref().unwrap().days(), 1);
+}
+
 #[allow(unused_must_use)]
 #[tokio::test]
 async fn role_manager_test() {